@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Flat `key=value` text store for sensor wiring, calibration offsets, and rule source.
+///
+/// One pair per line; blank lines and lines starting with `#` are ignored. This lets an
+/// operator reconfigure hardware (`temp.address`, `ph.address`, ...), tweak calibration
+/// offsets (`temp.cal.offset`), or point the rule engine at a new program (`rules.source`)
+/// by editing a file on the controller's storage, without recompiling.
+pub struct Config {
+    path: PathBuf,
+    data: HashMap<String, String>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    ParseError(usize, String),
+    MissingKey(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "config I/O error: {}", e),
+            ConfigError::ParseError(line, text) => write!(f, "config parse error on line {}: {:?}", line, text),
+            ConfigError::MissingKey(key) => write!(f, "missing config key {:?}", key),
+        }
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(error: io::Error) -> Self {
+        ConfigError::Io(error)
+    }
+}
+
+impl Config {
+    /// Load the store from `path`, or start empty if the file doesn't exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, ConfigError> {
+        let path = path.into();
+        let data = match fs::read_to_string(&path) {
+            Ok(contents) => parse(&contents)?,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { path, data })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.data.get(key).map(String::as_str)
+    }
+
+    /// Like `get`, but parses an I²C address written as decimal (`102`) or hex (`0x66`).
+    pub fn get_address(&self, key: &str) -> Result<u8, ConfigError> {
+        let raw = self.get(key).ok_or_else(|| ConfigError::MissingKey(key.to_string()))?;
+        let address = match raw.strip_prefix("0x") {
+            Some(hex) => u8::from_str_radix(hex, 16),
+            None => raw.parse::<u8>(),
+        };
+        address.map_err(|_| ConfigError::ParseError(0, raw.to_string()))
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.data.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.data.remove(key)
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.data.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Write the current contents back to `path`, one sorted `key=value` line per pair.
+    pub fn flush(&self) -> Result<(), ConfigError> {
+        let mut keys: Vec<&String> = self.data.keys().collect();
+        keys.sort();
+
+        let mut contents = String::new();
+        for key in keys {
+            contents.push_str(key);
+            contents.push('=');
+            contents.push_str(&self.data[key]);
+            contents.push('\n');
+        }
+
+        fs::write(&self.path, contents)?;
+        Ok(())
+    }
+}
+
+fn parse(contents: &str) -> Result<HashMap<String, String>, ConfigError> {
+    let mut data = HashMap::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(k), Some(v)) => {
+                data.insert(k.to_string(), v.to_string());
+            }
+            _ => return Err(ConfigError::ParseError(i + 1, line.to_string())),
+        }
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("rustyreef_config_test_{}_{}.cfg", name, std::process::id()))
+    }
+
+    #[test]
+    fn load_missing_file_starts_empty() {
+        let path = temp_path("missing");
+        let _ = fs::remove_file(&path);
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.get("temp.address"), None);
+    }
+
+    #[test]
+    fn set_get_remove_round_trip() {
+        let path = temp_path("round_trip");
+        let mut config = Config::load(&path).unwrap();
+        config.set("temp.address", "0x66");
+        assert_eq!(config.get("temp.address"), Some("0x66"));
+        assert_eq!(config.remove("temp.address"), Some("0x66".to_string()));
+        assert_eq!(config.get("temp.address"), None);
+    }
+
+    #[test]
+    fn flush_then_load_preserves_values() {
+        let path = temp_path("flush");
+        let mut config = Config::load(&path).unwrap();
+        config.set("temp.address", "0x66");
+        config.set("rules.source", "(if (< Tank_Temperature 78) 1 0)");
+        config.flush().unwrap();
+
+        let reloaded = Config::load(&path).unwrap();
+        assert_eq!(reloaded.get("temp.address"), Some("0x66"));
+        assert_eq!(reloaded.get("rules.source"), Some("(if (< Tank_Temperature 78) 1 0)"));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn get_address_parses_decimal_and_hex() {
+        let path = temp_path("address");
+        let mut config = Config::load(&path).unwrap();
+        config.set("temp.address", "0x66");
+        config.set("ph.address", "99");
+        assert_eq!(config.get_address("temp.address").unwrap(), 0x66);
+        assert_eq!(config.get_address("ph.address").unwrap(), 99);
+    }
+
+    #[test]
+    fn get_address_missing_key_errors() {
+        let path = temp_path("address_missing");
+        let config = Config::load(&path).unwrap();
+        assert!(matches!(config.get_address("temp.address"), Err(ConfigError::MissingKey(_))));
+    }
+
+    #[test]
+    fn blank_lines_and_comments_are_ignored() {
+        let data = parse("# wiring\ntemp.address=0x66\n\nph.address=0x63\n").unwrap();
+        assert_eq!(data.get("temp.address"), Some(&"0x66".to_string()));
+        assert_eq!(data.get("ph.address"), Some(&"0x63".to_string()));
+        assert_eq!(data.len(), 2);
+    }
+
+    #[test]
+    fn malformed_line_is_a_parse_error() {
+        let err = parse("not-a-pair").unwrap_err();
+        assert!(matches!(err, ConfigError::ParseError(1, _)));
+    }
+}
@@ -0,0 +1,258 @@
+use embedded_hal::blocking::{delay, i2c};
+
+/// Describes potential errors
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// I²C bus error
+    I2c,
+    /// Still processing, not ready
+    NotReady,
+    /// Syntax Error
+    SyntaxError,
+    /// Error parsing response
+    ParseError,
+}
+
+impl From<std::num::ParseFloatError> for Error {
+    fn from(_error: std::num::ParseFloatError) -> Self {
+        Error::ParseError
+    }
+}
+
+impl From<std::num::ParseIntError> for Error {
+    fn from(_error: std::num::ParseIntError) -> Self {
+        Error::ParseError
+    }
+}
+
+/// A calibration point understood by `EzoDevice::calibrate`.
+///
+/// Circuits vary in how many points they support: `Single` covers RTD/ORP/DO single-point
+/// calibration, while `Low`/`Mid`/`High` cover the pH/EC multi-point schemes. A circuit
+/// that doesn't recognize a given point rejects it with `Error::SyntaxError`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CalPoint {
+    /// `Cal,<value>`
+    Single(f64),
+    /// `Cal,low,<value>`
+    Low(f64),
+    /// `Cal,mid,<value>`
+    Mid(f64),
+    /// `Cal,high,<value>`
+    High(f64),
+}
+
+impl CalPoint {
+    fn command(&self) -> String {
+        match self {
+            CalPoint::Single(v) => format!("Cal,{}", v),
+            CalPoint::Low(v) => format!("Cal,low,{}", v),
+            CalPoint::Mid(v) => format!("Cal,mid,{}", v),
+            CalPoint::High(v) => format!("Cal,high,{}", v),
+        }
+    }
+}
+
+/// Shared command/response plumbing for every Atlas Scientific EZO circuit.
+///
+/// Every EZO circuit (pH, EC, DO, ORP, RTD, ...) speaks the same I²C framing: write an
+/// ASCII command, wait for the conversion, then read back a response whose first byte is
+/// a status code. `EzoDevice` owns that framing so each circuit wrapper only has to parse
+/// its own reply payload.
+pub struct EzoDevice<I2C, Delay> {
+    /// I2C master device to use to communicate with the sensor
+    i2c: I2C,
+    /// Delay device to be able to sleep in-between commands
+    delay: Delay,
+    /// I2C address
+    address: u8,
+}
+
+impl<I2C, Delay, HalI2CError> EzoDevice<I2C, Delay>
+where
+    I2C: i2c::Read<Error = HalI2CError> + i2c::Write<Error = HalI2CError>,
+    Delay: delay::DelayMs<u16>,
+{
+    pub fn new(i2c: I2C, delay: Delay, address: u8) -> Self {
+        Self { i2c, delay, address }
+    }
+
+    pub(crate) fn send_command(&mut self, command: &str) -> Result<(), Error> {
+        self.i2c.write(self.address, command.as_bytes()).map_err(|_| Error::I2c)
+    }
+
+    pub(crate) fn read_response(&mut self, mut buf: &mut [u8]) -> Result<(), Error> {
+        self.i2c.read(self.address, &mut buf).map_err(|_| Error::I2c)?;
+        self.validate_response_code(buf)
+    }
+
+    fn validate_response_code(&self, buf: &[u8]) -> Result<(), Error> {
+        match buf[0] {
+            254 => Err(Error::NotReady),
+            2 => Err(Error::SyntaxError),
+            1 => Ok(()),
+            _ => Err(Error::ParseError),
+        }
+    }
+
+    pub(crate) fn extract_string(&self, buf: &[u8]) -> Result<String, Error> {
+        let end = match buf.iter().position(|&r| r == 0x0) {
+            Some(n) => n,
+            None => buf.len()
+        };
+
+        match String::from_utf8((&buf[1..end]).to_vec()) {
+            Ok(t) => Ok(t),
+            Err(_) => Err(Error::ParseError)
+        }
+    }
+
+    pub(crate) fn delay_ms(&mut self, ms: u16) {
+        self.delay.delay_ms(ms)
+    }
+
+    /// Record a calibration point.
+    pub fn calibrate(&mut self, point: CalPoint) -> Result<(), Error> {
+        self.send_command(&point.command())?;
+        self.delay_ms(600);
+
+        let mut buffer = [0u8; 14];
+        self.read_response(&mut buffer)
+    }
+
+    /// Wipe all stored calibration points.
+    pub fn clear_calibration(&mut self) -> Result<(), Error> {
+        self.send_command("Cal,clear")?;
+        self.delay_ms(300);
+
+        let mut buffer = [0u8; 14];
+        self.read_response(&mut buffer)
+    }
+
+    /// Number of calibration points currently stored on the circuit, parsed from the
+    /// `?CAL,n` reply to `Cal,?`.
+    pub fn calibration_status(&mut self) -> Result<u8, Error> {
+        self.send_command("Cal,?")?;
+        self.delay_ms(300);
+
+        let mut buffer = [0u8; 14];
+        self.read_response(&mut buffer)?;
+        let reply = self.extract_string(&buffer)?;
+        let points = reply.rsplit(',').next().ok_or(Error::ParseError)?;
+
+        Ok(points.parse::<u8>()?)
+    }
+
+    /// Tell the circuit the current process temperature so it can compensate its reading.
+    pub fn set_compensation_temperature(&mut self, celsius: f64) -> Result<(), Error> {
+        self.send_command(&format!("T,{}", celsius))?;
+        self.delay_ms(300);
+
+        let mut buffer = [0u8; 14];
+        self.read_response(&mut buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::ErrorKind;
+
+    use embedded_hal_mock::delay::MockNoop as NoopDelay;
+    use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction};
+    use embedded_hal_mock::MockError;
+
+    /// Test if the `send_command` function sends the expected bytes to the expected address
+    #[test]
+    fn send_command() {
+        let expectations = [
+            Transaction::write(0x66, "i".as_bytes().to_vec()),
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut device = EzoDevice::new(mock, NoopDelay, 0x66);
+        let res = device.send_command("i");
+        assert!(res.is_ok());
+    }
+
+    /// Test whether the `send_command` function propagates I²C errors.
+    #[test]
+    fn send_command_error() {
+        let expectations = [
+            Transaction::write(0x66, "i".as_bytes().to_vec()).with_error(MockError::Io(ErrorKind::Other))
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut device = EzoDevice::new(mock, NoopDelay, 0x66);
+        let err = device.send_command("i").unwrap_err();
+        assert_eq!(err, Error::I2c);
+    }
+
+    /// Test if `read_response` succeeds and fills the buffer if first byte is 1
+    #[test]
+    fn read_response_success() {
+        let expectations = [
+            Transaction::read(0x66, vec![1,4]),
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut device = EzoDevice::new(mock, NoopDelay, 0x66);
+        let mut buf = vec![0u8, 2];
+        device.read_response(&mut buf).unwrap();
+        assert_eq!(buf, vec![1, 4]);
+    }
+
+    /// Test if `read_response` returns syntax if first byte is 2
+    #[test]
+    fn read_response_syntax_error() {
+        let expectations = [
+            Transaction::read(0x66, vec![2,4]),
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut device = EzoDevice::new(mock, NoopDelay, 0x66);
+        let mut buf = vec![0u8, 2];
+        let err = device.read_response(&mut buf).unwrap_err();
+        assert_eq!(err, Error::SyntaxError);
+    }
+
+    /// Test if `read_response` returns not ready if first byte is 254
+    #[test]
+    fn read_response_not_ready() {
+        let expectations = [
+            Transaction::read(0x66, vec![254,4]),
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut device = EzoDevice::new(mock, NoopDelay, 0x66);
+        let mut buf = vec![0u8, 2];
+        let err = device.read_response(&mut buf).unwrap_err();
+        assert_eq!(err, Error::NotReady);
+    }
+
+    /// Test if `read_response` returns parse error if first byte is other
+    #[test]
+    fn read_response_parse_error() {
+        let expectations = [
+            Transaction::read(0x66, vec![4,4]),
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut device = EzoDevice::new(mock, NoopDelay, 0x66);
+        let mut buf = vec![0u8, 2];
+        let err = device.read_response(&mut buf).unwrap_err();
+        assert_eq!(err, Error::ParseError);
+    }
+
+    /// Test if `calibration_status` parses the number of stored points out of `?CAL,n`
+    #[test]
+    fn calibration_status_parses_point_count() {
+        let mut response = vec![1];
+        response.extend_from_slice("?CAL,2".as_bytes());
+        response.resize(14, 0);
+
+        let expectations = [
+            Transaction::write(0x66, "Cal,?".as_bytes().to_vec()),
+            Transaction::read(0x66, response),
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut device = EzoDevice::new(mock, NoopDelay, 0x66);
+        let points = device.calibration_status().unwrap();
+        assert_eq!(points, 2);
+    }
+}
@@ -0,0 +1,66 @@
+use embedded_hal::blocking::{delay, i2c};
+
+use crate::io::ezo::{CalPoint, EzoDevice, Error};
+
+/// Atlas Scientific EZO DO (dissolved oxygen) circuit
+pub struct EzoDo<I2C, Delay> {
+    device: EzoDevice<I2C, Delay>,
+}
+
+impl<I2C, Delay, HalI2CError> EzoDo<I2C, Delay>
+where
+    I2C: i2c::Read<Error = HalI2CError> + i2c::Write<Error = HalI2CError>,
+    Delay: delay::DelayMs<u16>,
+{
+    pub fn new(i2c: I2C, delay: Delay, address: u8) -> Self {
+        Self { device: EzoDevice::new(i2c, delay, address) }
+    }
+
+    pub fn read(&mut self) -> Result<f64, Error> {
+        self.device.send_command("R")?;
+        self.device.delay_ms(600);
+
+        let mut buffer = [0u8; 14];
+        self.device.read_response(&mut buffer)?;
+        let mg_per_l = self.device.extract_string(&buffer)?.parse::<f64>()?;
+
+        Ok(mg_per_l)
+    }
+
+    pub fn calibrate(&mut self, mg_per_l: f64) -> Result<(), Error> {
+        self.device.calibrate(CalPoint::Single(mg_per_l))
+    }
+
+    pub fn clear_calibration(&mut self) -> Result<(), Error> {
+        self.device.clear_calibration()
+    }
+
+    pub fn calibration_status(&mut self) -> Result<u8, Error> {
+        self.device.calibration_status()
+    }
+
+    pub fn set_compensation_temperature(&mut self, celsius: f64) -> Result<(), Error> {
+        self.device.set_compensation_temperature(celsius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use embedded_hal_mock::delay::MockNoop as NoopDelay;
+    use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction};
+
+    /// Test if `read` returns expected value
+    #[test]
+    fn read_success() {
+        let expectations = [
+            Transaction::write(0x61, "R".as_bytes().to_vec()),
+            Transaction::read(0x61, vec![1, 56, 46, 50, 53, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut d_o = EzoDo::new(mock, NoopDelay, 0x61);
+        let res = d_o.read().unwrap();
+        assert_eq!(res, 8.25);
+    }
+}
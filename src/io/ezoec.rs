@@ -0,0 +1,117 @@
+use embedded_hal::blocking::{delay, i2c};
+
+use crate::io::ezo::{CalPoint, EzoDevice, Error};
+
+/// A single reading from the EZO EC circuit.
+///
+/// The circuit reports its enabled outputs as a single comma-separated string; with all
+/// outputs enabled that's conductivity, total dissolved solids, salinity, and specific
+/// gravity, in that order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EcReading {
+    /// Conductivity, in microsiemens per centimeter
+    pub conductivity: f64,
+    /// Total dissolved solids, in parts per million
+    pub tds: f64,
+    /// Salinity, in parts per thousand
+    pub salinity: f64,
+    /// Specific gravity
+    pub specific_gravity: f64,
+}
+
+/// Atlas Scientific EZO EC (conductivity) circuit
+pub struct EzoEc<I2C, Delay> {
+    device: EzoDevice<I2C, Delay>,
+}
+
+impl<I2C, Delay, HalI2CError> EzoEc<I2C, Delay>
+where
+    I2C: i2c::Read<Error = HalI2CError> + i2c::Write<Error = HalI2CError>,
+    Delay: delay::DelayMs<u16>,
+{
+    pub fn new(i2c: I2C, delay: Delay, address: u8) -> Self {
+        Self { device: EzoDevice::new(i2c, delay, address) }
+    }
+
+    pub fn read(&mut self) -> Result<EcReading, Error> {
+        self.device.send_command("R")?;
+        self.device.delay_ms(600);
+
+        let mut buffer = [0u8; 32];
+        self.device.read_response(&mut buffer)?;
+        let reading = self.device.extract_string(&buffer)?;
+
+        parse_reading(&reading)
+    }
+
+    pub fn calibrate_low(&mut self, microsiemens: f64) -> Result<(), Error> {
+        self.device.calibrate(CalPoint::Low(microsiemens))
+    }
+
+    pub fn calibrate_high(&mut self, microsiemens: f64) -> Result<(), Error> {
+        self.device.calibrate(CalPoint::High(microsiemens))
+    }
+
+    pub fn clear_calibration(&mut self) -> Result<(), Error> {
+        self.device.clear_calibration()
+    }
+
+    pub fn calibration_status(&mut self) -> Result<u8, Error> {
+        self.device.calibration_status()
+    }
+
+    pub fn set_compensation_temperature(&mut self, celsius: f64) -> Result<(), Error> {
+        self.device.set_compensation_temperature(celsius)
+    }
+}
+
+fn parse_reading(s: &str) -> Result<EcReading, Error> {
+    let fields: Vec<&str> = s.split(',').collect();
+    if fields.len() != 4 {
+        return Err(Error::ParseError);
+    }
+
+    Ok(EcReading {
+        conductivity: fields[0].parse::<f64>()?,
+        tds: fields[1].parse::<f64>()?,
+        salinity: fields[2].parse::<f64>()?,
+        specific_gravity: fields[3].parse::<f64>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use embedded_hal_mock::delay::MockNoop as NoopDelay;
+    use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction};
+
+    /// Test if `read` parses the comma-separated reading set
+    #[test]
+    fn read_success() {
+        let mut response = vec![1];
+        response.extend_from_slice("2500.00,1275.00,1.35,1.001".as_bytes());
+        response.resize(32, 0);
+
+        let expectations = [
+            Transaction::write(0x64, "R".as_bytes().to_vec()),
+            Transaction::read(0x64, response),
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut ec = EzoEc::new(mock, NoopDelay, 0x64);
+        let res = ec.read().unwrap();
+        assert_eq!(res, EcReading {
+            conductivity: 2500.00,
+            tds: 1275.00,
+            salinity: 1.35,
+            specific_gravity: 1.001,
+        });
+    }
+
+    /// Test if `read` reports a parse error when fewer outputs than expected are enabled
+    #[test]
+    fn read_wrong_field_count() {
+        let err = parse_reading("2500.00,1275.00").unwrap_err();
+        assert_eq!(err, Error::ParseError);
+    }
+}
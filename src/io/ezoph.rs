@@ -0,0 +1,74 @@
+use embedded_hal::blocking::{delay, i2c};
+
+use crate::io::ezo::{CalPoint, EzoDevice, Error};
+
+/// Atlas Scientific EZO pH circuit
+pub struct EzoPh<I2C, Delay> {
+    device: EzoDevice<I2C, Delay>,
+}
+
+impl<I2C, Delay, HalI2CError> EzoPh<I2C, Delay>
+where
+    I2C: i2c::Read<Error = HalI2CError> + i2c::Write<Error = HalI2CError>,
+    Delay: delay::DelayMs<u16>,
+{
+    pub fn new(i2c: I2C, delay: Delay, address: u8) -> Self {
+        Self { device: EzoDevice::new(i2c, delay, address) }
+    }
+
+    pub fn read(&mut self) -> Result<f64, Error> {
+        self.device.send_command("R")?;
+        self.device.delay_ms(900);
+
+        let mut buffer = [0u8; 14];
+        self.device.read_response(&mut buffer)?;
+        let ph = self.device.extract_string(&buffer)?.parse::<f64>()?;
+
+        Ok(ph)
+    }
+
+    pub fn calibrate_low(&mut self, ph: f64) -> Result<(), Error> {
+        self.device.calibrate(CalPoint::Low(ph))
+    }
+
+    pub fn calibrate_mid(&mut self, ph: f64) -> Result<(), Error> {
+        self.device.calibrate(CalPoint::Mid(ph))
+    }
+
+    pub fn calibrate_high(&mut self, ph: f64) -> Result<(), Error> {
+        self.device.calibrate(CalPoint::High(ph))
+    }
+
+    pub fn clear_calibration(&mut self) -> Result<(), Error> {
+        self.device.clear_calibration()
+    }
+
+    pub fn calibration_status(&mut self) -> Result<u8, Error> {
+        self.device.calibration_status()
+    }
+
+    pub fn set_compensation_temperature(&mut self, celsius: f64) -> Result<(), Error> {
+        self.device.set_compensation_temperature(celsius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use embedded_hal_mock::delay::MockNoop as NoopDelay;
+    use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction};
+
+    /// Test if `read` returns expected value
+    #[test]
+    fn read_success() {
+        let expectations = [
+            Transaction::write(0x63, "R".as_bytes().to_vec()),
+            Transaction::read(0x63, vec![1, 56, 46, 48, 48, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        ];
+        let mock = I2cMock::new(&expectations);
+        let mut ph = EzoPh::new(mock, NoopDelay, 0x63);
+        let res = ph.read().unwrap();
+        assert_eq!(res, 8.00);
+    }
+}
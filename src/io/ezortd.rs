@@ -1,32 +1,10 @@
 use embedded_hal::blocking::{delay, i2c};
 
-/// Describes potential errors
-#[derive(Debug, PartialEq)]
-pub enum Error {
-    /// I²C bus error
-    I2c,
-    /// Still processing, not ready
-    NotReady,
-    /// Syntax Error
-    SyntaxError,
-    /// Error parsing response
-    ParseError,
-}
+use crate::io::ezo::{CalPoint, EzoDevice, Error};
 
-impl From<std::num::ParseFloatError> for Error {
-    fn from(_error: std::num::ParseFloatError) -> Self {
-        Error::ParseError
-    }
-}
-
-/// Sensor configuration
+/// Atlas Scientific EZO RTD (temperature) circuit
 pub struct EzoRtd<I2C, Delay> {
-    /// I2C master device to use to communicate with the sensor
-    i2c: I2C,
-    /// Delay device to be able to sleep in-between commands
-    delay: Delay,
-    /// I2C address
-    address: u8,
+    device: EzoDevice<I2C, Delay>,
 }
 
 impl<I2C, Delay, HalI2CError> EzoRtd<I2C, Delay>
@@ -35,170 +13,150 @@ where
     Delay: delay::DelayMs<u16>,
 {
     pub fn new(i2c: I2C, delay: Delay, address: u8) -> Self {
-        Self { i2c, delay, address }
+        Self { device: EzoDevice::new(i2c, delay, address) }
     }
 
-    fn send_command(&mut self, command: &str) -> Result<(), Error> {
-        self.i2c.write(self.address, command.as_bytes()).map_err(|_| Error::I2c)
-    }
+    pub fn information(&mut self) -> Result<String, Error> {
+        self.device.send_command("i")?;
+        self.device.delay_ms(600);
+
+        let mut buffer = [0u8; 14];
+        self.device.read_response(&mut buffer)?;
+        let temperature_string = self.device.extract_string(&buffer)?;
 
-    fn read_response(&mut self, mut buf: &mut [u8]) -> Result<(), Error> {
-        self.i2c.read(self.address, &mut buf).map_err(|_| Error::I2c)?;
-        self.validate_response_code(buf)
+        Ok(temperature_string)
     }
 
-    fn validate_response_code(&self, buf: &[u8]) -> Result<(), Error> {
-        match buf[0] {
-            254 => Err(Error::NotReady),
-            2 => Err(Error::SyntaxError),
-            1 => Ok(()),
-            _ => Err(Error::ParseError),
-        }
+    /// Issue the `"R"` command that starts a temperature conversion, without waiting on it.
+    ///
+    /// Pair with [`poll_read`](Self::poll_read) to drive several sensors cooperatively
+    /// instead of blocking for the full conversion time.
+    pub fn start_read(&mut self) -> Result<(), Error> {
+        self.device.send_command("R")
     }
 
-    fn extract_string(&self, buf: &[u8]) -> Result<String, Error> {
-        let end = match buf.iter().position(|&r| r == 0x0) {
-            Some(n) => n,
-            None => buf.len()
-        };
+    /// Check whether a conversion started by [`start_read`](Self::start_read) has finished.
+    ///
+    /// Performs a single I²C read with no delay. Returns `Error::NotReady` if the circuit
+    /// hasn't finished converting yet; the caller decides whether and how long to wait
+    /// before polling again.
+    pub fn poll_read(&mut self) -> Result<f64, Error> {
+        let mut buffer = [0u8; 14];
+        self.device.read_response(&mut buffer)?;
+        let temperature = self.device.extract_string(&buffer)?.parse::<f64>()?;
 
-        match String::from_utf8((&buf[1..end]).to_vec()) {
-            Ok(t) => Ok(t),
-            Err(_) => Err(Error::ParseError)
-        }
+        Ok(temperature)
     }
 
-    pub fn information(&mut self) -> Result<String, Error> {
-        self.send_command("i")?;
-        self.delay.delay_ms(600);
+    /// Read the temperature, retrying on `Error::NotReady` with a caller-configurable
+    /// back-off instead of sleeping for a single fixed delay.
+    ///
+    /// Waits `initial_delay_ms` after issuing the read, then polls up to `max_attempts`
+    /// times, adding `backoff_ms` to the wait before each subsequent attempt. Returns the
+    /// last `Error::NotReady` if the circuit never finishes converting within the budget.
+    pub fn read_with_retry(
+        &mut self,
+        initial_delay_ms: u16,
+        max_attempts: u8,
+        backoff_ms: u16,
+    ) -> Result<f64, Error> {
+        self.start_read()?;
+
+        let mut delay_ms = initial_delay_ms;
+        let mut last_err = Error::NotReady;
+        for _ in 0..max_attempts {
+            self.device.delay_ms(delay_ms);
+            match self.poll_read() {
+                Ok(temperature) => return Ok(temperature),
+                Err(Error::NotReady) => {
+                    last_err = Error::NotReady;
+                    delay_ms += backoff_ms;
+                }
+                Err(e) => return Err(e),
+            }
+        }
 
-        let mut buffer = [0u8; 14];
-        self.read_response(&mut buffer)?;
-        let temperature_string = self.extract_string(&buffer)?;
-        
-        Ok(temperature_string)
+        Err(last_err)
     }
 
     pub fn read(&mut self) -> Result<f64, Error> {
-        self.send_command("R")?;
-        self.delay.delay_ms(600);
-
-        let mut buffer = [0u8; 14];
-        self.read_response(&mut buffer)?;
-        let temperature = self.extract_string(&buffer)?.parse::<f64>()?;
-
-        Ok(temperature)
+        self.read_with_retry(600, 1, 0)
     }
 
     pub fn status(&mut self) -> Result<String, Error> {
-        self.send_command("Status")?;
-        self.delay.delay_ms(300);
-        
+        self.device.send_command("Status")?;
+        self.device.delay_ms(300);
+
         let mut buffer = [0u8; 14];
-        self.read_response(&mut buffer)?;
-        let s = self.extract_string(&buffer)?;
-        
+        self.device.read_response(&mut buffer)?;
+        let s = self.device.extract_string(&buffer)?;
+
         Ok(s)
     }
+
+    /// Calibrate the circuit at the given process temperature.
+    pub fn calibrate(&mut self, celsius: f64) -> Result<(), Error> {
+        self.device.calibrate(CalPoint::Single(celsius))
+    }
+
+    pub fn clear_calibration(&mut self) -> Result<(), Error> {
+        self.device.clear_calibration()
+    }
+
+    pub fn calibration_status(&mut self) -> Result<u8, Error> {
+        self.device.calibration_status()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use std::io::ErrorKind;
-
     use embedded_hal_mock::delay::MockNoop as NoopDelay;
     use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction};
-    use embedded_hal_mock::MockError;
-
-    /// Test if the `send_command` function sends the expected bytes to the expected address
-    #[test]
-    fn send_command() {
-        let expectations = [
-            Transaction::write(0x66, "i".as_bytes().to_vec()),
-        ];
-        let mock = I2cMock::new(&expectations);
-        let mut rtd = EzoRtd::new(mock, NoopDelay, 0x66);
-        let res = rtd.send_command("i");
-        assert!(res.is_ok());
-    }
 
-    /// Test whether the `send_command` function propagates I²C errors.
-    #[test]
-    fn send_command_error() {
-        let expectations = [
-            Transaction::write(0x66, "i".as_bytes().to_vec()).with_error(MockError::Io(ErrorKind::Other))
-        ];
-        let mock = I2cMock::new(&expectations);
-        let mut rtd = EzoRtd::new(mock, NoopDelay, 0x66);
-        let err = rtd.send_command("i").unwrap_err();
-        assert_eq!(err, Error::I2c);
-    }
-
-    /// Test if `read_response` returns string if first byte is 1
-    #[test]
-    fn read_response_success() {
-        let expectations = [
-            Transaction::read(0x66, vec![1,4]),
-        ];
-        let mock = I2cMock::new(&expectations);
-        let mut rtd = EzoRtd::new(mock, NoopDelay, 0x66);
-        let mut buf = vec![0u8, 2];
-        rtd.read_response(&mut buf).unwrap();
-        assert_eq!(buf, vec![1, 4]);
-    }
-
-    /// Test if `read_response` returns syntax if first byte is 2
-    #[test]
-    fn read_response_syntax_error() {
-        let expectations = [
-            Transaction::read(0x66, vec![2,4]),
-        ];
-        let mock = I2cMock::new(&expectations);
-        let mut rtd = EzoRtd::new(mock, NoopDelay, 0x66);
-        let mut buf = vec![0u8, 2];
-        let err = rtd.read_response(&mut buf).unwrap_err();
-        assert_eq!(err, Error::SyntaxError);
-    }
-
-    /// Test if `read_response` returns not ready if first byte is 254
+    /// Test if `read` returns expected value
     #[test]
-    fn read_response_not_ready() {
+    fn read_success() {
         let expectations = [
-            Transaction::read(0x66, vec![254,4]),
+            Transaction::write(0x66, "R".as_bytes().to_vec()),
+            Transaction::read(0x66, vec![1, 49, 50, 46, 51, 52, 53, 0, 0, 0, 0, 0, 0, 0]),
         ];
         let mock = I2cMock::new(&expectations);
         let mut rtd = EzoRtd::new(mock, NoopDelay, 0x66);
-        let mut buf = vec![0u8, 2];
-        let err = rtd.read_response(&mut buf).unwrap_err();
-        assert_eq!(err, Error::NotReady);
+        let res = rtd.read().unwrap();
+        assert_eq!(res, 12.345);
     }
 
-    /// Test if `read_response` returns parse error if first byte is other
+    /// Test that `read_with_retry` keeps polling through `Error::NotReady` and returns the
+    /// value once the circuit finishes converting.
     #[test]
-    fn read_response_parse_error() {
+    fn read_with_retry_retries_until_ready() {
         let expectations = [
-            Transaction::read(0x66, vec![4,4]),
+            Transaction::write(0x66, "R".as_bytes().to_vec()),
+            Transaction::read(0x66, vec![254, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Transaction::read(0x66, vec![254, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Transaction::read(0x66, vec![1, 49, 50, 46, 51, 52, 53, 0, 0, 0, 0, 0, 0, 0]),
         ];
         let mock = I2cMock::new(&expectations);
         let mut rtd = EzoRtd::new(mock, NoopDelay, 0x66);
-        let mut buf = vec![0u8, 2];
-        let err = rtd.read_response(&mut buf).unwrap_err();
-        assert_eq!(err, Error::ParseError);
+        let res = rtd.read_with_retry(600, 3, 100).unwrap();
+        assert_eq!(res, 12.345);
     }
 
-    /// Test if `read` returns expected value
+    /// Test that `read_with_retry` surfaces the last `Error::NotReady` once `max_attempts`
+    /// is exhausted, instead of looping forever or returning some other error.
     #[test]
-    fn read_success() {
+    fn read_with_retry_exhausts_attempts() {
         let expectations = [
             Transaction::write(0x66, "R".as_bytes().to_vec()),
-            Transaction::read(0x66, vec![1, 49, 50, 46, 51, 52, 53, 0, 0, 0, 0, 0, 0, 0]),
+            Transaction::read(0x66, vec![254, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Transaction::read(0x66, vec![254, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
         ];
         let mock = I2cMock::new(&expectations);
         let mut rtd = EzoRtd::new(mock, NoopDelay, 0x66);
-        let res = rtd.read().unwrap();
-        assert_eq!(res, 12.345);
+        let err = rtd.read_with_retry(600, 2, 100).unwrap_err();
+        assert_eq!(err, Error::NotReady);
     }
 
 }
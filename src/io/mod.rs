@@ -0,0 +1,6 @@
+pub mod ezo;
+pub mod ezodo;
+pub mod ezoec;
+pub mod ezoorp;
+pub mod ezoph;
+pub mod ezortd;
@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use chrono::{Local, NaiveDateTime};
+use log::{Level, Log, Metadata, Record};
+
+/// One entry captured by a [`RingLogger`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub timestamp: NaiveDateTime,
+    pub event: Event,
+}
+
+/// A diagnostic event worth keeping around after the fact.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A probe reading, e.g. `Tank_Temperature` -> `78.2`.
+    Reading { probe: String, value: f64 },
+    /// An error surfaced while talking to a probe or evaluating a rule.
+    Error(String),
+    /// The output a control rule decided on.
+    Rule { output: String },
+    /// A message emitted through the `log` facade (`log::debug!` and friends).
+    Message { level: Level, message: String },
+}
+
+/// Bounded in-memory logger that retains the most recent `capacity` entries.
+///
+/// Install it as the backing store behind the `log` facade with [`install`] so every
+/// `log::debug!`/`log::warn!`/... call in the crate lands here instead of only going to
+/// stderr. Probes and the rule engine can also [`record`](Self::record) structured events
+/// directly. An operator without a live console attached can pull
+/// [`snapshot`](Self::snapshot) to see what the controller was doing right before a probe
+/// failed or a rule misfired.
+pub struct RingLogger {
+    capacity: usize,
+    entries: Mutex<VecDeque<Entry>>,
+}
+
+impl RingLogger {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Record a structured diagnostic event, evicting the oldest entry if at capacity.
+    pub fn record(&self, event: Event) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(Entry {
+            timestamp: Local::now().naive_local(),
+            event,
+        });
+    }
+
+    /// The buffered entries, newest first.
+    pub fn snapshot(&self) -> Vec<Entry> {
+        let entries = self.entries.lock().unwrap();
+        entries.iter().rev().cloned().collect()
+    }
+}
+
+impl Log for RingLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.record(Event::Message {
+            level: record.level(),
+            message: format!("{}", record.args()),
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install a `RingLogger` of the given `capacity` as the global logger for the `log`
+/// facade, and return a handle to it for calling [`RingLogger::snapshot`] later.
+///
+/// Should be called once, near the start of `main`; a second call panics because the
+/// `log` facade only accepts one global logger for the life of the process.
+pub fn install(capacity: usize) -> &'static RingLogger {
+    let logger: &'static RingLogger = Box::leak(Box::new(RingLogger::new(capacity)));
+    log::set_logger(logger).expect("a logger is already installed");
+    log::set_max_level(log::LevelFilter::Debug);
+    logger
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_and_snapshot_newest_first() {
+        let logger = RingLogger::new(10);
+        logger.record(Event::Reading { probe: "Tank_Temperature".to_string(), value: 78.2 });
+        logger.record(Event::Rule { output: "1".to_string() });
+
+        let snapshot = logger.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].event, Event::Rule { output: "1".to_string() });
+        assert_eq!(snapshot[1].event, Event::Reading { probe: "Tank_Temperature".to_string(), value: 78.2 });
+    }
+
+    #[test]
+    fn evicts_oldest_entry_once_at_capacity() {
+        let logger = RingLogger::new(2);
+        logger.record(Event::Error("NotReady".to_string()));
+        logger.record(Event::Error("NotReady".to_string()));
+        logger.record(Event::Rule { output: "0".to_string() });
+
+        let snapshot = logger.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].event, Event::Rule { output: "0".to_string() });
+        assert_eq!(snapshot[1].event, Event::Error("NotReady".to_string()));
+    }
+
+    #[test]
+    fn log_trait_impl_buffers_facade_messages() {
+        let logger = RingLogger::new(10);
+        let record = Record::builder()
+            .level(Level::Debug)
+            .args(format_args!("builtin_op add 1 and 1"))
+            .build();
+        logger.log(&record);
+
+        let snapshot = logger.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].event, Event::Message {
+            level: Level::Debug,
+            message: "builtin_op add 1 and 1".to_string(),
+        });
+    }
+}
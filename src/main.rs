@@ -1,63 +1,102 @@
-use rlua::Lua;
-use rlua::Result;
-use rlua::Function;
+mod config;
+mod io;
+mod logging;
+mod risp;
 
-fn main() -> Result<()> {
+use std::thread;
+use std::time::Duration;
+
+use config::Config;
+use log::debug;
+use logging::Event;
+use risp::{env::Env, eval::eval, parse::parse, val::*};
+
+/// Operator-editable `key=value` config file (wiring, calibration, rules). See [`config::Config`].
+const CONFIG_PATH: &str = "rustyreef.cfg";
+
+/// Rule evaluated until an operator sets `rules.source` in the config file.
+const DEFAULT_RULE: &str = "(if (< Tank_Temperature 78) 1 (if (> Tank_Temperature 80) 0 Heater_Outlet))";
+
+/// How long to sleep between control-loop passes.
+const LOOP_INTERVAL: Duration = Duration::from_secs(5);
+
+fn main() {
     println!(r" ___         _          ___          __ ");
     println!(r"| _ \_  _ __| |_ _  _  | _ \___ ___ / _|");
     println!(r"|   / || (_-<  _| || | |   / -_) -_)  _|");
     println!(r"|_|_\\_,_/__/\__|\_, | |_|_\___\___|_|  ");
-    println!(r"                 |__/                   "); 
-
-
-    let lua = Lua::new();
-
-    lua.context(|lua_ctx| {
-        let globals = lua_ctx.globals();
-
-        let inputs = lua_ctx.create_table()?;
-        inputs.set("Tank_Temperature", 75.9)?;
-        globals.set("inputs", inputs)?;
-
-        let outputs = lua_ctx.create_table()?;
-        outputs.set("Heater_Outlet", 2)?;
-        globals.set("outputs", outputs)?;
-
-        lua_ctx.load(
-            r#"
-            function protect(tbl)
-                return setmetatable({}, {
-                    __index = tbl,
-                    __newindex = function(t, key, value)
-                        error("attempting to change constant " ..
-                            tostring(key) .. " to " .. tostring(value), 2)
-                    end
-                })
-            end
-            inputs = protect(inputs)
-            outputs = protect(outputs)
-            "#
-        ).exec()?;
-
-        let blah = lua_ctx
-            .load(
-                r#"
-                    if inputs.Tank_Temperature < 78 then
-                        return 1
-                    elseif inputs.Tank_Temperature > 80 then
-                        return 0
-                    else
-                        return outputs.Heater_Outlet
-                    end
-            "#,
-            )
-            .eval::<i32>()?;
-        
-        
-        print!("*** {:?}", blah);
-
-        Ok(())
-    })?;
-
-    Ok(())
+    println!(r"                 |__/                   ");
+
+    let log = logging::install(256);
+
+    let config = match Config::load(CONFIG_PATH) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("failed to load config {:?}: {}", CONFIG_PATH, err);
+            return;
+        }
+    };
+
+    // Validating the addresses here only catches an operator's config mistake early; it
+    // does not mean they're wired to a device. Nothing downstream in this binary reads
+    // `temp_address`/`heater_address` to talk to hardware (no `EzoRtd`/other circuit
+    // wrapper is constructed here) — `tank_temperature`/`heater_outlet` below stay fixed
+    // until that wiring exists.
+    let temp_address = config.get_address("temp.address").unwrap_or(0x66);
+    let heater_address = config.get_address("heater.address").unwrap_or(0x01);
+    debug!("temp probe at {:#x}, heater output at {:#x}", temp_address, heater_address);
+    if temp_address == heater_address {
+        eprintln!(
+            "temp.address and heater.address both resolve to {:#x}; check {:?}",
+            temp_address, CONFIG_PATH
+        );
+        return;
+    }
+
+    // Stub control loop: this binary doesn't talk to an actual probe or actuator. A real
+    // controller would call `EzoRtd::new(i2c, delay, temp_address).read()` here instead of
+    // a fixed `tank_temperature`, and would need some actuator abstraction (none exists in
+    // this crate yet) to apply the rule's `outlet` decision rather than only logging and
+    // printing it below.
+    let tank_temperature = 75.9;
+    let heater_outlet = 2;
+
+    loop {
+        log.record(Event::Reading { probe: "Tank_Temperature".to_string(), value: tank_temperature });
+
+        let mut env = Env::new(None);
+        env.add_constant("Tank_Temperature", val_float(tank_temperature));
+        env.add_constant("Heater_Outlet", val_num(heater_outlet));
+
+        // Reload the rule every pass so an operator's edit to `rules.source` takes effect
+        // on the controller's next iteration instead of requiring a restart.
+        let rule_source = match Config::load(CONFIG_PATH) {
+            Ok(current) => current.get("rules.source").unwrap_or(DEFAULT_RULE).to_string(),
+            Err(err) => {
+                eprintln!("failed to reload config {:?}: {}", CONFIG_PATH, err);
+                config.get("rules.source").unwrap_or(DEFAULT_RULE).to_string()
+            }
+        };
+        let mut rule = match parse(&rule_source) {
+            Ok(p) => p,
+            Err(err) => {
+                eprintln!("failed to parse rule: {}", err);
+                thread::sleep(LOOP_INTERVAL);
+                continue;
+            }
+        };
+
+        match eval(&mut env, &mut rule) {
+            Ok(outlet) => {
+                log.record(Event::Rule { output: format!("{:?}", outlet) });
+                println!("*** {:?}", outlet);
+            }
+            Err(err) => {
+                log.record(Event::Error(format!("{}", err)));
+                eprintln!("rule evaluation failed: {}", err);
+            }
+        }
+
+        thread::sleep(LOOP_INTERVAL);
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::risp::{
     error::RispError,
@@ -7,15 +7,47 @@ use crate::risp::{
     val::*,
 };
 
-#[derive(Debug, PartialEq)]
+/// Governs what `+`/`-`/`*` do when an `i64` result would overflow. Defaults to `Error`
+/// so a wraparound doesn't silently corrupt a value that then drives a rule decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wrap around using two's-complement semantics, like `i64::wrapping_add`.
+    Wrap,
+    /// Clamp to `i64::MIN`/`i64::MAX`, like `i64::saturating_add`.
+    Saturate,
+    /// Return `RispError::Overflow` instead of producing a corrupted value.
+    Error,
+}
+
+impl Default for OverflowPolicy {
+    fn default() -> Self {
+        OverflowPolicy::Error
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Env {
     data: HashMap<String, Box<Val>>,
+    /// Names bound with `add_constant` (e.g. live sensor readings). A rule can read them
+    /// through `get` but `put` rejects rebinding them, mirroring the `protect` metatable
+    /// the old Lua control loop used to guard its `inputs`/`outputs` tables.
+    protected: HashSet<String>,
+    /// Enclosing scope, if any. `get` walks up this chain on a miss instead of failing
+    /// immediately, which is what lets a `lambda` body or a `let` block see names bound
+    /// outside it.
+    parent: Option<Box<Env>>,
+    /// How `+`/`-`/`*` handle `i64` overflow. Inherited by child scopes created with
+    /// `with_parent` so a `let` block or lambda call doesn't silently revert to the default.
+    overflow_policy: OverflowPolicy,
 }
 
 impl Env {
     pub fn new(data: Option<HashMap<String, Box<Val>>>) -> Self {
         let mut ret = Self {
-            data: data.unwrap_or_default()
+            data: data.unwrap_or_default(),
+            protected: HashSet::new(),
+            parent: None,
+            overflow_policy: OverflowPolicy::default(),
         };
         ret.add_builtin("add", builtin_add);
         ret.add_builtin("+", builtin_add);
@@ -41,11 +73,21 @@ impl Env {
         ret.add_builtin("==", builtin_eq);
         ret.add_builtin("ne", builtin_ne);
         ret.add_builtin("!=", builtin_ne);
-        ret.add_builtin("if", builtin_if);
+        ret.add_builtin("&", builtin_band);
+        ret.add_builtin("|", builtin_bor);
+        ret.add_builtin("^", builtin_bxor);
         ret.add_builtin("now", builtin_now);
-        ret.add_builtin("and", builtin_and);
-        ret.add_builtin("or", builtin_or);
         ret.add_builtin("not", builtin_not);
+        ret.add_builtin("len", builtin_len);
+        ret.add_builtin("substr", builtin_substr);
+        ret.add_builtin("format", builtin_format);
+        ret.add_builtin("seconds", builtin_seconds);
+        ret.add_builtin("hours", builtin_hours);
+        ret.add_builtin("minutes", builtin_minutes);
+        ret.add_builtin("days", builtin_days);
+        ret.add_builtin("to-string", builtin_to_string);
+        ret.add_builtin("to-num", builtin_to_num);
+        ret.add_builtin("type-of", builtin_type_of);
 
         // add constants
         ret.add_constant("true", val_bool(true));
@@ -55,29 +97,59 @@ impl Env {
         ret
     }
 
+    /// Create a child scope nested inside `parent`, used for a `let` block or to evaluate
+    /// a called lambda's body. A lookup that misses in the child falls through to `parent`.
+    pub fn with_parent(parent: Env) -> Self {
+        let overflow_policy = parent.overflow_policy;
+        Self {
+            data: HashMap::new(),
+            protected: HashSet::new(),
+            parent: Some(Box::new(parent)),
+            overflow_policy,
+        }
+    }
+
     fn add_builtin(&mut self, name: &str, func: Builtin) {
-        self.put(name.to_string(), val_builtin(func, name))
+        self.data.insert(name.to_string(), val_builtin(func, name));
     }
 
-    fn add_constant(&mut self, name: &str, val: Box<Val>) {
-        self.put(name.to_string(), val);
+    /// Bind `name` to `val` as a protected constant: readable via `get`, but `put` refuses
+    /// to rebind it. Used for language constants (`true`/`false`/`nil`) and for live
+    /// sensor readings injected into the control-loop environment before a rule runs.
+    pub fn add_constant(&mut self, name: &str, val: Box<Val>) {
+        self.data.insert(name.to_string(), val);
+        self.protected.insert(name.to_string());
     }
 
-    pub fn put(&mut self, name: String, val: Box<Val>) {
+    pub fn put(&mut self, name: String, val: Box<Val>) -> RispResult {
+        if self.protected.contains(&name) {
+            return Err(RispError::ProtectedBinding(name));
+        }
+
         let current = self.data.entry(name).or_insert_with(|| val.clone());
         if *val != **current {
             // if it already existed, overwrite it with v
-            *current = val;
+            *current = val.clone();
         }
+        Ok(val)
     }
 
     pub fn get(&self, k: &str) -> RispResult {
         match self.data.get(k) {
             Some(v) => Ok(v.clone()),
-            None => {
-                Err(RispError::UnknownFunction(k.to_string()))
-            }
+            None => match &self.parent {
+                Some(parent) => parent.get(k),
+                None => Err(RispError::UnknownFunction(k.to_string())),
+            },
         }
     }
 
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow_policy = policy;
+    }
+
 }
\ No newline at end of file
@@ -7,10 +7,13 @@ use std::{
 #[derive(Debug)]
 pub enum RispError {
     ArgumentMismatch,
+    CharOverflow(String),
     NoChildren,
     NotANumber,
     NumArguments(usize, usize),
+    Overflow(String),
     ParseError(String),
+    ProtectedBinding(String),
     UnknownFunction(String),
     WrongType(String, String),
 }
@@ -20,6 +23,7 @@ impl fmt::Display for RispError {
         use RispError::*;
         match self {
             ArgumentMismatch => write!(f, "Argument mismatch"),
+            CharOverflow(s) => write!(f, "Char overflow: {}", s),
             NoChildren => write!(f, "Val has no children"),
             NotANumber => write!(f, "NaN"),
             NumArguments(expected, received) => write!(
@@ -27,7 +31,9 @@ impl fmt::Display for RispError {
                 "Wrong number of arguments: expected {}, received {}",
                 expected, received
             ),
+            Overflow(s) => write!(f, "Integer overflow: {}", s),
             ParseError(s) => write!(f, "Parse error: {}", s),
+            ProtectedBinding(s) => write!(f, "Cannot rebind protected constant {}", s),
             UnknownFunction(s) => write!(f, "Unknown function {}", s),
             WrongType(expected, received) => write!(f, "Wrong type: expected {}, received {}", expected, received),
         }
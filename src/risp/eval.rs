@@ -1,12 +1,12 @@
 use log::debug;
-use std::collections::{HashSet};
-use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::convert::TryFrom;
+use std::ops::Add;
 use std::cmp::Ordering;
 
-use chrono::{Duration, NaiveDate, NaiveTime, offset::Local};
+use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, offset::Local};
 
 use crate::risp::{
-    env::Env,
+    env::{Env, OverflowPolicy},
     error::RispError,
     result::{RispResult},
     val::*,
@@ -34,39 +34,441 @@ fn call(e: &mut Env, f: Val, args: &mut Val) -> RispResult {
                 ValFun::Builtin(_name, fp) => {
                     return fp(e, args);
                 }
+                ValFun::Lambda { params, body, closure, name } => call_lambda(params, body, *closure, name, args),
             }
         },
         _ => Err(RispError::WrongType("Function".to_string(), format!("{:?}", f))),
     }
 }
 
-// macro to shorten code for applying a binary operation to two Lvals
-macro_rules! apply_binop {
-    ( $op:ident, $x:ident, $y:ident ) => {
-        match (*$x, *$y) {
-            (Val::Num(x_num), Val::Num(y_num)) => {
-                $x = val_num(x_num.$op(y_num));
-                continue;
+fn call_lambda(params: Vec<String>, mut body: Box<Val>, closure: Env, name: Option<String>, args: &mut Val) -> RispResult {
+    let arg_count = match *args {
+        Val::List(ref children) => children.len(),
+        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", args))),
+    };
+    if arg_count != params.len() {
+        return Err(RispError::NumArguments(params.len(), arg_count));
+    }
+
+    // If this lambda was bound with `def`, rebind its own name inside the call scope so
+    // a recursive self-call (e.g. `(def fact (lambda (n) ... (fact (- n 1)) ...))`)
+    // resolves, even though the closure captured at `def` time predates that binding.
+    let self_ref = name.as_ref().map(|n| {
+        val_lambda_named(params.clone(), body.clone(), closure.clone(), n.clone())
+    });
+
+    let mut scope = Env::with_parent(closure);
+    if let (Some(n), Some(f)) = (name, self_ref) {
+        scope.put(n, f)?;
+    }
+    for param in params {
+        let arg = val_pop(args, 0)?;
+        scope.put(param, arg)?;
+    }
+
+    eval(&mut scope, &mut body)
+}
+
+// Name of the symbol at the head of this list, if any, so `eval` can spot a special
+// form. Special forms (`lambda`, `def`,
+// `let`, `and`, `or`, `if`, `cond`) control which of their own arguments get evaluated, so
+// they're handled here instead of going through the eager `eval_cells` path every other
+// list takes.
+fn special_form_name(cells: &[Box<Val>]) -> Option<&str> {
+    match cells.get(0).map(|c| &**c) {
+        Some(Val::Sym(s)) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+// `(lambda (params...) body)` / `(fn (params...) body)` — captures the defining
+// environment and returns a callable `Val::Fun` without evaluating `body`.
+fn eval_lambda(e: &Env, cells: &mut Vec<Box<Val>>) -> RispResult {
+    if cells.len() != 3 {
+        return Err(RispError::NumArguments(2, cells.len() - 1));
+    }
+
+    let body = cells.remove(2);
+    let params_val = cells.remove(1);
+    let params = match *params_val {
+        Val::List(children) => {
+            let mut names = Vec::with_capacity(children.len());
+            for child in children {
+                match *child {
+                    Val::Sym(s) => names.push(s),
+                    other => return Err(RispError::WrongType("symbol".to_string(), format!("{:?}", other))),
+                }
+            }
+            names
+        }
+        other => return Err(RispError::WrongType("list".to_string(), format!("{:?}", other))),
+    };
+
+    Ok(val_lambda(params, body, e.clone()))
+}
+
+// `(def name expr)` — evaluates `expr` and binds it to `name` in the current scope.
+fn eval_def(e: &mut Env, cells: &mut Vec<Box<Val>>) -> RispResult {
+    if cells.len() != 3 {
+        return Err(RispError::NumArguments(2, cells.len() - 1));
+    }
+
+    let mut expr = cells.remove(2);
+    let name = match *cells.remove(1) {
+        Val::Sym(s) => s,
+        other => return Err(RispError::WrongType("symbol".to_string(), format!("{:?}", other))),
+    };
+
+    let val = eval(e, &mut expr)?;
+    // Tag a freshly-defined lambda with the name it's being bound to, so it can see
+    // itself for recursion (see `call_lambda`).
+    let val = match *val {
+        Val::Fun(ValFun::Lambda { params, body, closure, .. }) => {
+            val_lambda_named(params, body, *closure, name.clone())
+        }
+        other => Box::new(other),
+    };
+    e.put(name, val.clone())?;
+    Ok(val)
+}
+
+// `(let ((a e1) (b e2) ...) body)` — evaluates each binding expression in turn (each one
+// can see the bindings before it) in a fresh child scope, then evaluates `body` there.
+fn eval_let(e: &mut Env, cells: &mut Vec<Box<Val>>) -> RispResult {
+    if cells.len() != 3 {
+        return Err(RispError::NumArguments(2, cells.len() - 1));
+    }
+
+    let mut body = cells.remove(2);
+    let bindings = match *cells.remove(1) {
+        Val::List(children) => children,
+        other => return Err(RispError::WrongType("list".to_string(), format!("{:?}", other))),
+    };
+
+    let mut scope = Env::with_parent(e.clone());
+    for binding in bindings {
+        let mut pair = match *binding {
+            Val::List(p) => p,
+            other => return Err(RispError::WrongType("binding pair".to_string(), format!("{:?}", other))),
+        };
+        if pair.len() != 2 {
+            return Err(RispError::NumArguments(2, pair.len()));
+        }
+
+        let mut value_expr = pair.remove(1);
+        let name = match *pair.remove(0) {
+            Val::Sym(s) => s,
+            other => return Err(RispError::WrongType("symbol".to_string(), format!("{:?}", other))),
+        };
+
+        let value = eval(&mut scope, &mut value_expr)?;
+        scope.put(name, value)?;
+    }
+
+    eval(&mut scope, &mut body)
+}
+
+// `(and e1 e2 ...)` — evaluates its arguments left-to-right, stopping as soon as one
+// evaluates to `false` (that `false` is the result) so later arguments, and any side
+// effects or errors in them, are never reached. If every argument is truthy, returns the
+// last one. `(and)` with no arguments is `true`.
+fn eval_and(e: &mut Env, cells: &mut Vec<Box<Val>>) -> RispResult {
+    let mut result = val_bool(true);
+    for mut expr in cells.drain(1..) {
+        result = eval(e, &mut expr)?;
+        if *result == Val::Bool(false) {
+            break;
+        }
+    }
+    Ok(result)
+}
+
+// `(or e1 e2 ...)` — evaluates its arguments left-to-right, stopping as soon as one
+// evaluates to something other than `false` (that value is the result) so later
+// arguments are never reached. `(or)` with no arguments is `false`.
+fn eval_or(e: &mut Env, cells: &mut Vec<Box<Val>>) -> RispResult {
+    let mut result = val_bool(false);
+    for mut expr in cells.drain(1..) {
+        result = eval(e, &mut expr)?;
+        if *result != Val::Bool(false) {
+            break;
+        }
+    }
+    Ok(result)
+}
+
+// `(if test then-expr [else-expr])` — evaluates only `test`, then evaluates and returns
+// exactly one of `then-expr`/`else-expr` depending on whether `test` is truthy (anything
+// but `false`/`nil`). `else-expr` is optional; omitting it yields `nil` on a falsy test.
+fn eval_if(e: &mut Env, cells: &mut Vec<Box<Val>>) -> RispResult {
+    if cells.len() < 3 || cells.len() > 4 {
+        return Err(RispError::NumArguments(3, cells.len() - 1));
+    }
+
+    let else_expr = if cells.len() == 4 { Some(cells.remove(3)) } else { None };
+    let mut then_expr = cells.remove(2);
+    let mut test_expr = cells.remove(1);
+
+    let test = eval(e, &mut test_expr)?;
+    if *test != Val::Bool(false) {
+        eval(e, &mut then_expr)
+    } else {
+        match else_expr {
+            Some(mut expr) => eval(e, &mut expr),
+            None => Ok(val_bool(false)),
+        }
+    }
+}
+
+// `(cond (test1 expr1) (test2 expr2) ... (else exprN))` — evaluates each clause's test in
+// order and returns the body of the first truthy one, never touching the tests or bodies
+// of later clauses. The symbol `else` in test position is never evaluated and always
+// matches, for a final default clause. Yields `nil` if no clause matches.
+fn eval_cond(e: &mut Env, cells: &mut Vec<Box<Val>>) -> RispResult {
+    for clause in cells.drain(1..) {
+        let mut pair = match *clause {
+            Val::List(p) => p,
+            other => return Err(RispError::WrongType("clause".to_string(), format!("{:?}", other))),
+        };
+        if pair.len() != 2 {
+            return Err(RispError::NumArguments(2, pair.len()));
+        }
+
+        let mut body = pair.remove(1);
+        let mut test = pair.remove(0);
+
+        let matched = match *test {
+            Val::Sym(ref s) if s == "else" => true,
+            _ => *eval(e, &mut test)? != Val::Bool(false),
+        };
+        if matched {
+            return eval(e, &mut body);
+        }
+    }
+    Ok(val_bool(false))
+}
+
+/// The operators `builtin_iter_op` folds pairwise over a list of arguments. Replaces a
+/// stringly-typed `&str` dispatch (and its trailing `unreachable!()`) with a type the
+/// compiler can prove is handled exhaustively.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Min,
+    Max,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    Eq,
+    /// Bitwise on integers, eager (non-short-circuiting) logical on booleans. Contrast
+    /// `and`/`or`, which are special forms that skip unevaluated arguments.
+    BitAnd,
+    BitOr,
+    BitXor,
+}
+
+impl Operator {
+    /// Whether this operator's final accumulated value collapses to a single `true`/`false`
+    /// once the whole chain has been folded (as opposed to arithmetic and `min`/`max`, which
+    /// carry a value forward).
+    fn is_comparison(&self) -> bool {
+        matches!(self, Operator::Gt | Operator::Lt | Operator::Ge | Operator::Le | Operator::Eq)
+    }
+
+    /// Whether a `false` returned by `apply` should stop the fold immediately, rather than
+    /// being a legitimate accumulated value (true only of `min`/`max`/comparisons). Excludes
+    /// `BitOr`/`BitXor` too: an intermediate `false` there isn't conclusive the way it is for
+    /// a comparison chain, since a later operand can still flip the result back to `true`.
+    fn short_circuits_on_false(&self) -> bool {
+        !matches!(
+            self,
+            Operator::Add | Operator::Sub | Operator::Mul | Operator::Div | Operator::Rem
+                | Operator::BitAnd | Operator::BitOr | Operator::BitXor
+        )
+    }
+
+    /// Fold `next` into the running accumulator `acc`, handling `i64` overflow on `Num`
+    /// operands according to `policy`.
+    pub fn apply(&self, acc: Val, next: Val, policy: OverflowPolicy) -> RispResult {
+        use Operator::*;
+        match self {
+            Add => match (acc, next) {
+                (Val::Str(x), Val::Str(y)) => Ok(val_str(&(x + &y))),
+                (Val::Num(x), Val::Num(y)) => {
+                    checked_int_op(policy, x, y, i64::checked_add, i64::saturating_add, i64::wrapping_add)
+                }
+                (Val::Num(x), Val::Float(y)) => Ok(val_float(x as f64 + y)),
+                (Val::Float(x), Val::Num(y)) => Ok(val_float(x + y as f64)),
+                (Val::Float(x), Val::Float(y)) => Ok(val_float(x + y)),
+                (Val::DateTime(dt), Val::Duration(d)) => Ok(val_datetime(dt + d)),
+                (Val::Duration(d), Val::DateTime(dt)) => Ok(val_datetime(dt + d)),
+                (Val::Duration(x), Val::Duration(y)) => Ok(val_duration(x + y)),
+                // `(+ char num)` advances the code point; `(+ num char)` instead yields a
+                // plain number, matching the asymmetric convention other small interpreters
+                // use for char/int addition.
+                (Val::Char(c), Val::Num(n)) => char_advance(c, n),
+                (Val::Num(n), Val::Char(c)) => n.checked_add(c as i64)
+                    .map(val_num)
+                    .ok_or_else(|| RispError::CharOverflow(format!("{} and {:?}", n, c))),
+                _ => Err(RispError::NotANumber),
+            },
+            Sub => match (acc, next) {
+                (Val::Num(x), Val::Num(y)) => {
+                    checked_int_op(policy, x, y, i64::checked_sub, i64::saturating_sub, i64::wrapping_sub)
+                }
+                (Val::Num(x), Val::Float(y)) => Ok(val_float(x as f64 - y)),
+                (Val::Float(x), Val::Num(y)) => Ok(val_float(x - y as f64)),
+                (Val::Float(x), Val::Float(y)) => Ok(val_float(x - y)),
+                (Val::DateTime(x), Val::DateTime(y)) => Ok(val_duration(x - y)),
+                (Val::Date(x), Val::Date(y)) => Ok(val_duration(x - y)),
+                (Val::DateTime(dt), Val::Duration(d)) => Ok(val_datetime(dt - d)),
+                (Val::Duration(x), Val::Duration(y)) => Ok(val_duration(x - y)),
+                // `(- char char)` is the code point distance; `(- char num)` shifts back.
+                (Val::Char(a), Val::Char(b)) => char_distance(a, b),
+                (Val::Char(c), Val::Num(n)) => {
+                    let delta = n.checked_neg()
+                        .ok_or_else(|| RispError::CharOverflow(format!("{:?} by -{}", c, n)))?;
+                    char_advance(c, delta)
+                }
+                _ => Err(RispError::NotANumber),
+            },
+            Mul => match (acc, next) {
+                (Val::Num(x), Val::Num(y)) => {
+                    checked_int_op(policy, x, y, i64::checked_mul, i64::saturating_mul, i64::wrapping_mul)
+                }
+                (Val::Num(x), Val::Float(y)) => Ok(val_float(x as f64 * y)),
+                (Val::Float(x), Val::Num(y)) => Ok(val_float(x * y as f64)),
+                (Val::Float(x), Val::Float(y)) => Ok(val_float(x * y)),
+                (Val::Duration(d), Val::Num(n)) => duration_mul(d, n),
+                (Val::Num(n), Val::Duration(d)) => duration_mul(d, n),
+                _ => Err(RispError::NotANumber),
+            },
+            Div => match (acc, next) {
+                (Val::Num(x), Val::Num(y)) => Ok(val_num(x / y)),
+                (Val::Num(x), Val::Float(y)) => Ok(val_float(x as f64 / y)),
+                (Val::Float(x), Val::Num(y)) => Ok(val_float(x / y as f64)),
+                (Val::Float(x), Val::Float(y)) => Ok(val_float(x / y)),
+                _ => Err(RispError::NotANumber),
             },
-            (Val::Num(x_num), Val::Float(y_num)) => {
-                $x = val_float((x_num as f64).$op(y_num));
-                continue;
+            Rem => match (acc, next) {
+                (Val::Num(x), Val::Num(y)) => Ok(val_num(x % y)),
+                (Val::Num(x), Val::Float(y)) => Ok(val_float(x as f64 % y)),
+                (Val::Float(x), Val::Num(y)) => Ok(val_float(x % y as f64)),
+                (Val::Float(x), Val::Float(y)) => Ok(val_float(x % y)),
+                _ => Err(RispError::NotANumber),
             },
-            (Val::Float(x_num), Val::Num(y_num)) => {
-                $x = val_float(x_num.$op((y_num as f64)));
-                continue;
+            // Unlike the `gt`/`lt`/etc. comparisons below, `min`/`max` carry a value forward
+            // rather than collapsing to a bool, so there's no sensible "false" to fall back
+            // to on an incomparable pair (e.g. a NaN float) — this still errors rather than
+            // silently picking one side.
+            Min => match acc.partial_cmp(&next) {
+                Some(Ordering::Less) => Ok(Box::new(acc)),
+                Some(Ordering::Greater) => Ok(Box::new(next)),
+                Some(Ordering::Equal) => Ok(Box::new(acc)),
+                None => Err(RispError::ArgumentMismatch),
             },
-            (Val::Float(x_num), Val::Float(y_num)) => {
-                $x = val_float(x_num.$op(y_num));
-                continue;
+            Max => match acc.partial_cmp(&next) {
+                Some(Ordering::Less) => Ok(Box::new(next)),
+                Some(Ordering::Greater) => Ok(Box::new(acc)),
+                Some(Ordering::Equal) => Ok(Box::new(acc)),
+                None => Err(RispError::ArgumentMismatch),
             },
-            _ => return Err(RispError::NotANumber),
+            Gt => Self::compare(acc, next, |o| o == Ordering::Greater),
+            Lt => Self::compare(acc, next, |o| o == Ordering::Less),
+            Ge => Self::compare(acc, next, |o| o != Ordering::Less),
+            Le => Self::compare(acc, next, |o| o != Ordering::Greater),
+            Eq => Self::compare(acc, next, |o| o == Ordering::Equal),
+            BitAnd => Self::bitwise(acc, next, "&", |x, y| x & y, |x, y| x & y),
+            BitOr => Self::bitwise(acc, next, "|", |x, y| x | y, |x, y| x | y),
+            BitXor => Self::bitwise(acc, next, "^", |x, y| x ^ y, |x, y| x ^ y),
         }
-    };
+    }
+
+    // `&`/`|`/`^` operate bit-by-bit on integers, and double as eager (non-short-circuiting)
+    // logical operators when both operands are booleans. Anything else is a clear error
+    // rather than a silent coercion.
+    fn bitwise(
+        acc: Val,
+        next: Val,
+        symbol: &str,
+        int_op: impl Fn(i64, i64) -> i64,
+        bool_op: impl Fn(bool, bool) -> bool,
+    ) -> RispResult {
+        match (acc, next) {
+            (Val::Num(x), Val::Num(y)) => Ok(val_num(int_op(x, y))),
+            (Val::Bool(x), Val::Bool(y)) => Ok(val_bool(bool_op(x, y))),
+            (acc, next) => Err(RispError::WrongType(
+                format!("{}: expected boolean or integer operands", symbol),
+                format!("{} and {}", acc, next),
+            )),
+        }
+    }
+
+    // `None` means the pair is unordered (e.g. a NaN float, or two values of different
+    // types) rather than an error: IEEE 754 says every comparison involving NaN is false,
+    // so `gt`/`lt`/`ge`/`le`/`eq` treat an incomparable pair the same as an ordered pair
+    // that simply didn't satisfy `holds`, instead of aborting the whole chain.
+    fn compare(acc: Val, next: Val, holds: impl Fn(Ordering) -> bool) -> RispResult {
+        match acc.partial_cmp(&next) {
+            Some(ord) if holds(ord) => Ok(Box::new(next)),
+            _ => Ok(val_bool(false)),
+        }
+    }
+}
+
+/// Shift `c`'s Unicode scalar value by `delta` code points, for `(+ char num)` and the
+/// `(- char num)` cipher-shift case (where the caller negates `delta`). Unlike the `i64`
+/// overflow policy above, this always errors rather than wrapping or saturating: there's no
+/// sensible "clamped" or "wrapped" character once you've stepped past the valid scalar range.
+fn char_advance(c: char, delta: i64) -> RispResult {
+    let overflow = || RispError::CharOverflow(format!("{:?} by {}", c, delta));
+    let new_code = (c as i64).checked_add(delta).ok_or_else(overflow)?;
+    let new_code = u32::try_from(new_code).map_err(|_| overflow())?;
+    char::from_u32(new_code).map(val_char).ok_or_else(overflow)
 }
 
-// apply a binary operation operation to a list of arguments in succession
-fn builtin_iter_op(mut v: &mut Val, func: &str) -> RispResult {
+/// The signed distance, in code points, between two chars, for `(- char char)`.
+fn char_distance(a: char, b: char) -> RispResult {
+    (a as i64).checked_sub(b as i64)
+        .map(val_num)
+        .ok_or_else(|| RispError::CharOverflow(format!("{:?} and {:?}", a, b)))
+}
+
+/// Apply a checked/saturating/wrapping `i64` op according to `policy`, used by `+`/`-`/`*`
+/// so an operation that would otherwise wrap silently (e.g. `i64::MAX + 1`) either errors
+/// or clamps instead, per the chosen `OverflowPolicy`.
+fn checked_int_op(
+    policy: OverflowPolicy,
+    x: i64,
+    y: i64,
+    checked: fn(i64, i64) -> Option<i64>,
+    saturating: fn(i64, i64) -> i64,
+    wrapping: fn(i64, i64) -> i64,
+) -> RispResult {
+    match policy {
+        OverflowPolicy::Error => match checked(x, y) {
+            Some(n) => Ok(val_num(n)),
+            None => Err(RispError::Overflow(format!("{} and {}", x, y))),
+        },
+        OverflowPolicy::Saturate => Ok(val_num(saturating(x, y))),
+        OverflowPolicy::Wrap => Ok(val_num(wrapping(x, y))),
+    }
+}
+
+/// Multiply a `Duration` by an `i64` scalar. `Duration`'s own `*` takes `i32`, so reject a
+/// multiplier outside that range with an error instead of silently truncating it via `as`.
+fn duration_mul(d: Duration, n: i64) -> RispResult {
+    let n = i32::try_from(n).map_err(|_| RispError::Overflow(format!("duration and {}", n)))?;
+    Ok(val_duration(d * n))
+}
+
+// fold an operator over a list of arguments in succession
+fn builtin_iter_op(mut v: &mut Val, op: Operator, policy: OverflowPolicy) -> RispResult {
     let mut child_count = match *v {
         Val::List(ref children) => children.len(),
         _ => return Ok(Box::new(v.clone())),
@@ -75,148 +477,33 @@ fn builtin_iter_op(mut v: &mut Val, func: &str) -> RispResult {
     let mut x = val_pop(&mut v, 0)?;
 
     // If no args given and we're doing subtraction, perform unary negation
-    if func == "sub" && child_count == 1 {
+    if op == Operator::Sub && child_count == 1 {
         debug!("builtin_op: Unary negation on {}", x);
         let x_num = x.as_num()?;
-        return Ok(val_num(-x_num));
+        return match policy {
+            OverflowPolicy::Error => match x_num.checked_neg() {
+                Some(n) => Ok(val_num(n)),
+                None => Err(RispError::Overflow(format!("-{}", x_num))),
+            },
+            OverflowPolicy::Saturate => Ok(val_num(x_num.saturating_neg())),
+            OverflowPolicy::Wrap => Ok(val_num(x_num.wrapping_neg())),
+        };
     }
 
     // consume the children until empty and operate on x
     while child_count > 1 {
         let y = val_pop(&mut v, 0)?;
         child_count -= 1;
-        match func {
-            "add" => {
-                debug!("builtin_op add {} and {}", x, y);
-                apply_binop!(add, x, y);
-            },
-            "sub" => {
-                debug!("builtin_op sub {} and {}", x, y);
-                apply_binop!(sub, x, y);
-            },
-            "mul" => {
-                debug!("builtin_op mul {} and {}", x, y);
-                apply_binop!(mul, x, y);
-            },
-            "div" => {
-                debug!("builtin_op mul {} and {}", x, y);
-                apply_binop!(div, x, y);
-            },
-            "rem" => {
-                debug!("builtin_op rem {} and {}", x, y);
-                apply_binop!(rem, x, y);
-            },
-            "min" => {
-                debug!("builtin_op min {} and {}", x, y);
-                match x.partial_cmp(&y) {
-                    Some(o) => {
-                        match o {
-                            Ordering::Less => {
-                                x = x;
-                            },
-                            Ordering::Greater => {
-                                x = y;
-                            }
-                            _ => return Ok(val_bool(false))
-                        }
-                    },
-                    None => return Err(RispError::ArgumentMismatch)
-                }
-            },
-            "max" => {
-                debug!("builtin_op max {} and {}", x, y);
-                match x.partial_cmp(&y) {
-                    Some(o) => {
-                        match o {
-                            Ordering::Less => {
-                                x = y;
-                            },
-                            Ordering::Greater => {
-                                x = x;
-                            }
-                            _ => return Ok(val_bool(false))
-                        }
-                    },
-                    None => return Err(RispError::ArgumentMismatch)
-                }
-            },
-            "gt" => {
-                debug!("builtin_op gt {} and {}", x, y);
-                match x.partial_cmp(&y) {
-                    Some(o) => {
-                        match o {
-                            Ordering::Greater => {
-                                x = y;
-                            },
-                            _ => return Ok(val_bool(false))
-                        }
-                    },
-                    None => return Err(RispError::ArgumentMismatch)
-                }
-            },
-            "lt" => {
-                debug!("builtin_op lt {} and {}", x, y);
-                match x.partial_cmp(&y) {
-                    Some(o) => {
-                        match o {
-                            Ordering::Less => {
-                                x = x;
-                            },
-                            _ => return Ok(val_bool(false))
-                        }
-                    },
-                    None => return Err(RispError::ArgumentMismatch)
-                }
-            },
-            "ge" => {
-                debug!("builtin_op ge {} and {}", x, y);
-                match x.partial_cmp(&y) {
-                    Some(o) => {
-                        match o {
-                            Ordering::Greater |
-                            Ordering::Equal  => {
-                                x = y;
-                            },
-                            _ => return Ok(val_bool(false))
-                        }
-                    },
-                    None => return Err(RispError::ArgumentMismatch)
-                }
-            },
-            "le" => {
-                debug!("builtin_op le {} and {}", x, y);
-                match x.partial_cmp(&y) {
-                    Some(o) => {
-                        match o {
-                            Ordering::Less |
-                            Ordering::Equal  => {
-                                x = y;
-                            },
-                            _ => return Ok(val_bool(false))
-                        }
-                    },
-                    None => return Err(RispError::ArgumentMismatch)
-                }
-            },
-            "eq" => {
-                debug!("builtin_op le {} and {}", x, y);
-                match x.partial_cmp(&y) {
-                    Some(o) => {
-                        match o {
-                            Ordering::Equal  => {
-                                x = y;
-                            },
-                            _ => return Ok(val_bool(false))
-                        }
-                    },
-                    None => return Err(RispError::ArgumentMismatch)
-                }
-            },
-            _ => unreachable!(),
+        debug!("builtin_op {:?} {} and {}", op, x, y);
+
+        let result = op.apply(*x, *y, policy)?;
+        if op.short_circuits_on_false() && *result == Val::Bool(false) {
+            return Ok(result);
         }
+        x = result;
     }
 
-    if func == "gt" || func == "lt" || func == "ge" || func == "le" || func == "eq" {
+    if op.is_comparison() {
         Ok(val_bool(true))
     } else {
         Ok(x)
@@ -241,6 +528,17 @@ pub fn eval(e: &mut Env, v: &mut Val) -> RispResult {
            return Ok(result);
         }
         Val::List(ref mut cells) => {
+            match special_form_name(cells) {
+                Some("lambda") | Some("fn") => return eval_lambda(e, cells),
+                Some("def") => return eval_def(e, cells),
+                Some("let") => return eval_let(e, cells),
+                Some("and") => return eval_and(e, cells),
+                Some("or") => return eval_or(e, cells),
+                Some("if") => return eval_if(e, cells),
+                Some("cond") => return eval_cond(e, cells),
+                _ => {}
+            }
+
             debug!("eval: List, evaluating children");
             child_count = cells.len();
             args_eval = eval_cells(e, cells)?;
@@ -267,52 +565,67 @@ pub fn eval(e: &mut Env, v: &mut Val) -> RispResult {
     }
 }
 
-pub fn builtin_add(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "add")
+pub fn builtin_add(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Add, e.overflow_policy())
 }
 
-pub fn builtin_sub(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "sub")
+pub fn builtin_sub(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Sub, e.overflow_policy())
 }
 
-pub fn builtin_mul(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "mul")
+pub fn builtin_mul(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Mul, e.overflow_policy())
 }
 
-pub fn builtin_div(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "div")
+pub fn builtin_div(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Div, e.overflow_policy())
 }
 
-pub fn builtin_rem(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "rem")
+pub fn builtin_rem(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Rem, e.overflow_policy())
 }
 
-pub fn builtin_min(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "min")
+pub fn builtin_min(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Min, e.overflow_policy())
 }
 
-pub fn builtin_max(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "max")
+pub fn builtin_max(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Max, e.overflow_policy())
 }
 
-pub fn builtin_gt(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "gt")
+pub fn builtin_gt(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Gt, e.overflow_policy())
 }
 
-pub fn builtin_lt(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "lt")
+pub fn builtin_lt(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Lt, e.overflow_policy())
 }
 
-pub fn builtin_ge(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "ge")
+pub fn builtin_ge(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Ge, e.overflow_policy())
 }
 
-pub fn builtin_le(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "le")
+pub fn builtin_le(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Le, e.overflow_policy())
 }
 
-pub fn builtin_eq(_e: &mut Env, a: &mut Val) -> RispResult {
-    builtin_iter_op(a, "eq")
+pub fn builtin_eq(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::Eq, e.overflow_policy())
+}
+
+// `(& x y ...)` — bitwise AND on integers, eager logical AND on booleans.
+pub fn builtin_band(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::BitAnd, e.overflow_policy())
+}
+
+// `(| x y ...)` — bitwise OR on integers, eager logical OR on booleans.
+pub fn builtin_bor(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::BitOr, e.overflow_policy())
+}
+
+// `(^ x y ...)` — bitwise XOR on integers, eager logical XOR on booleans.
+pub fn builtin_bxor(e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_iter_op(a, Operator::BitXor, e.overflow_policy())
 }
 
 pub fn builtin_ne(_e: &mut Env, mut a: &mut Val) -> RispResult {
@@ -321,157 +634,216 @@ pub fn builtin_ne(_e: &mut Env, mut a: &mut Val) -> RispResult {
         _ => return Ok(Box::new(a.clone())),
     };
 
-    let mut values = HashSet::new();
+    let mut values: Vec<Val> = Vec::new();
     let x = val_pop(&mut a, 0)?;
-    values.insert(x.as_num()?);
+    values.push(*x);
     while child_count > 1 {
         let y = val_pop(&mut a, 0)?;
         child_count -= 1;
-        let y_num = y.as_num()?;
-        if !values.contains(&y_num) {
-            values.insert(y_num);
-        } else {
+        if values.contains(&*y) {
             return Ok(val_bool(false));
         }
+        values.push(*y);
     }
     return Ok(val_bool(true));
 }
 
-pub fn builtin_if(e: &mut Env, a: &mut Val) -> RispResult {
-    // must have three children
+pub fn builtin_now(_e: &mut Env, a: &mut Val) -> RispResult {
+    // must have zero children
     let child_count = match *a {
         Val::List(ref children) => children.len(),
         _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", a)))
     };
-    if child_count != 3 {
-        return Err(RispError::NumArguments(3, child_count));
+    if child_count != 0 {
+        return Err(RispError::NumArguments(0, child_count));
     }
+    let now = Local::now().naive_local();
+    debug!("Now {}", now);
+    
+    return Ok(val_datetime(now));
+}
 
-    // first child must evaluate to bool
-    let b = match *val_pop(a, 0)? {
-        Val::Bool(b) => b,
-        Val::List(cells) => {
-            match *eval_cells(e, &cells)? {
-                Val::Bool(b) => b,
-                _ => return Err(RispError::WrongType("bool".to_string(),format!("{:?}", ""))) // todo improve this error    
+pub fn builtin_not(e: &mut Env, v: &mut Val) -> RispResult {
+    // must have 1 arg
+    let mut arg_count = match *v {
+        Val::List(ref children) => {
+            let ret = children.len();
+            if ret != 1 {
+                return Err(RispError::NumArguments(1, ret));
             }
+            ret
         },
-        _ => { 
-            return Err(RispError::WrongType("bool".to_string(),format!("{:?}", ""))); // todo improve this error
-        }
+        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", v)))
     };
+
+    let res = *eval(e, &mut *val_pop(v,0)?)?;
     
-    let mut expr_to_eval;
-    if b {
-        expr_to_eval = val_pop(a, 0)?;
-    } else {
-        expr_to_eval = val_pop(a, 1)?;
+    match res {
+        Val::Bool(b) => Ok(val_bool(!b)),
+        _ => Ok(val_bool(false))
     }
-
-    eval(e, &mut expr_to_eval)
 }
 
-pub fn builtin_now(_e: &mut Env, a: &mut Val) -> RispResult {
-    // must have zero children
+// `(len s)` — the number of characters in a string (or elements in a list).
+pub fn builtin_len(_e: &mut Env, a: &mut Val) -> RispResult {
     let child_count = match *a {
         Val::List(ref children) => children.len(),
-        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", a)))
+        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", a))),
     };
-    if child_count != 0 {
-        return Err(RispError::NumArguments(0, child_count));
+    if child_count != 1 {
+        return Err(RispError::NumArguments(1, child_count));
+    }
+
+    match *val_pop(a, 0)? {
+        Val::Str(s) => Ok(val_num(s.chars().count() as i64)),
+        Val::List(children) => Ok(val_num(children.len() as i64)),
+        other => Err(RispError::WrongType("string or list".to_string(), format!("{:?}", other))),
     }
-    let now = Local::now().naive_local();
-    debug!("Now {}", now);
-    
-    return Ok(val_datetime(now));
 }
 
-pub fn builtin_and(e: &mut Env, v: &mut Val) -> RispResult {
-    // must have more than 1 arg
-    let mut arg_count = match *v {
+// `(substr s start end)` — the characters of `s` in `[start, end)`.
+pub fn builtin_substr(_e: &mut Env, a: &mut Val) -> RispResult {
+    let child_count = match *a {
         Val::List(ref children) => children.len(),
-        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", v)))
+        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", a))),
     };
-    if arg_count < 2 {
-        return Err(RispError::NumArguments(2, arg_count));
+    if child_count != 3 {
+        return Err(RispError::NumArguments(3, child_count));
     }
 
-    // all but the last arg must eval non nil/false
-    while arg_count > 1 {
-        let res = *eval(e, &mut *val_pop(v,0)?)?;
-        match res {
-            Val::Bool(b) => {
-                if !b {
-                    return Ok(val_bool(false));
-                }
-            },
-            _ => { }
-        }
-        arg_count -= 1;
+    let s = match *val_pop(a, 0)? {
+        Val::Str(s) => s,
+        other => return Err(RispError::WrongType("string".to_string(), format!("{:?}", other))),
+    };
+    let start = val_pop(a, 0)?.as_num()? as usize;
+    let end = val_pop(a, 0)?.as_num()? as usize;
+    if start > end || end > s.chars().count() {
+        return Err(RispError::ArgumentMismatch);
     }
 
-    // if you got here, all args evaluated true, evaluate the last arg
-    let mut last_arg = val_pop(v, 0)?;
-    eval(e, &mut last_arg)
+    Ok(val_str(&s.chars().skip(start).take(end - start).collect::<String>()))
 }
 
-pub fn builtin_or(e: &mut Env, v: &mut Val) -> RispResult {
-    // must have more than 1 arg
-    let mut arg_count = match *v {
-        Val::List(ref children) => {
-            let ret = children.len();
-            if ret < 2 {
-                return Err(RispError::NumArguments(2, ret));
-            }
-            ret
-        },
-        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", v)))
+// `(format a b c ...)` — concatenates the printed form of each argument into one string,
+// for building labels and messages out of readings and rule results.
+pub fn builtin_format(_e: &mut Env, a: &mut Val) -> RispResult {
+    let child_count = match *a {
+        Val::List(ref children) => children.len(),
+        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", a))),
     };
 
-    // at least one except the last arg must eval non nil/false
-    let mut one_true = false;
-    while arg_count > 1 {
-        let res = *eval(e, &mut *val_pop(v,0)?)?;
-        match res {
-            Val::Bool(b) => {
-                if b {
-                    one_true = true;
-                }
-            },
-            _ => { 
-                one_true = true;
-            }
-        }
-        arg_count -= 1;
+    let mut out = String::new();
+    for _ in 0..child_count {
+        out.push_str(&format!("{}", val_pop(a, 0)?));
     }
+    Ok(val_str(&out))
+}
 
-    if one_true {
-        let mut last_arg = val_pop(v, 0)?;
-        return eval(e, &mut last_arg);
-    } else {
-        return Ok(val_bool(false));
+// `(to-string x)` — the textual form of any value: booleans as `true`/`false`, datetimes as
+// RFC3339 (`builtin_now` stores wall-clock local time with no offset attached, so re-attach
+// the system's current local UTC offset here rather than either mislabeling it `Z` or
+// emitting a designator-less string that RFC3339 doesn't allow), and everything else via its
+// normal `Display`.
+pub fn builtin_to_string(_e: &mut Env, a: &mut Val) -> RispResult {
+    let child_count = match *a {
+        Val::List(ref children) => children.len(),
+        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", a))),
+    };
+    if child_count != 1 {
+        return Err(RispError::NumArguments(1, child_count));
     }
+
+    let s = match *val_pop(a, 0)? {
+        Val::DateTime(dt) => Local.from_local_datetime(&dt).single()
+            .map(|local| local.to_rfc3339())
+            .unwrap_or_else(|| dt.format("%Y-%m-%dT%H:%M:%S").to_string()),
+        other => format!("{}", other),
+    };
+    Ok(val_str(&s))
 }
 
-pub fn builtin_not(e: &mut Env, v: &mut Val) -> RispResult {
-    // must have 1 arg
-    let mut arg_count = match *v {
-        Val::List(ref children) => {
-            let ret = children.len();
-            if ret != 1 {
-                return Err(RispError::NumArguments(1, ret));
-            }
-            ret
+// `(to-num s)` — parses a numeric string into a `Num` or `Float` (whichever fits), or
+// passes a value that's already numeric through unchanged.
+pub fn builtin_to_num(_e: &mut Env, a: &mut Val) -> RispResult {
+    let child_count = match *a {
+        Val::List(ref children) => children.len(),
+        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", a))),
+    };
+    if child_count != 1 {
+        return Err(RispError::NumArguments(1, child_count));
+    }
+
+    match *val_pop(a, 0)? {
+        Val::Num(n) => Ok(val_num(n)),
+        Val::Float(f) => Ok(val_float(f)),
+        Val::Str(s) => match s.parse::<i64>() {
+            Ok(n) => Ok(val_num(n)),
+            Err(_) => Ok(val_float(s.parse::<f64>()?)),
         },
-        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", v)))
+        other => Err(RispError::WrongType("string".to_string(), format!("{:?}", other))),
+    }
+}
+
+// `(type-of x)` — the name of x's `Val` variant, using the same names as elsewhere in error
+// messages (e.g. `"string"`, `"bool"`, `"list"`).
+pub fn builtin_type_of(_e: &mut Env, a: &mut Val) -> RispResult {
+    let child_count = match *a {
+        Val::List(ref children) => children.len(),
+        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", a))),
     };
+    if child_count != 1 {
+        return Err(RispError::NumArguments(1, child_count));
+    }
 
-    let res = *eval(e, &mut *val_pop(v,0)?)?;
-    
-    match res {
-        Val::Bool(b) => Ok(val_bool(!b)),
-        _ => Ok(val_bool(false))
+    let name = match *val_pop(a, 0)? {
+        Val::Bool(_) => "bool",
+        Val::Char(_) => "char",
+        Val::Float(_) => "float",
+        Val::Fun(_) => "function",
+        Val::List(_) => "list",
+        Val::Num(_) => "num",
+        Val::Risp(_) => "risp",
+        Val::Str(_) => "string",
+        Val::Sym(_) => "symbol",
+        Val::Time(_) => "time",
+        Val::Date(_) => "date",
+        Val::DateTime(_) => "datetime",
+        Val::Duration(_) => "duration",
+    };
+    Ok(val_str(name))
+}
+
+fn builtin_duration_literal(a: &mut Val, make: fn(i64) -> Duration) -> RispResult {
+    let child_count = match *a {
+        Val::List(ref children) => children.len(),
+        _ => return Err(RispError::WrongType("list".to_string(), format!("{:?}", a))),
+    };
+    if child_count != 1 {
+        return Err(RispError::NumArguments(1, child_count));
     }
+
+    let n = val_pop(a, 0)?.as_num()?;
+    Ok(val_duration(make(n)))
+}
+
+// `(seconds n)` — an n-second Duration.
+pub fn builtin_seconds(_e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_duration_literal(a, Duration::seconds)
+}
+
+// `(hours n)` — an n-hour Duration, for offsetting a Datetime or comparing against one.
+pub fn builtin_hours(_e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_duration_literal(a, Duration::hours)
+}
+
+// `(minutes n)` — an n-minute Duration.
+pub fn builtin_minutes(_e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_duration_literal(a, Duration::minutes)
+}
+
+// `(days n)` — an n-day Duration.
+pub fn builtin_days(_e: &mut Env, a: &mut Val) -> RispResult {
+    builtin_duration_literal(a, Duration::days)
 }
 
 #[cfg(test)]
@@ -496,7 +868,7 @@ mod test {
     fn eval_symbol() {
         init();
         let mut env = Env::new(None);
-        env.put("a".to_string(), val_num(1));
+        env.put("a".to_string(), val_num(1)).unwrap();
         assert_eval("a", &mut env, val_num(1));
     }
 
@@ -525,10 +897,47 @@ mod test {
     fn add_numbers_and_symbols() {
         init();
         let mut env = Env::new(None);
-        env.put("a".to_string(), val_num(1));
+        env.put("a".to_string(), val_num(1)).unwrap();
         assert_eval("(add 1 1 a)", &mut env, val_num(3));
     }
 
+    #[test]
+    fn add_concatenates_strings() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(+ \"low \" \"tank\")", &mut env, val_str("low tank"));
+    }
+
+    #[test]
+    fn strings_compare_lexicographically() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(< \"a\" \"b\")", &mut env, val_bool(true));
+        assert_eval("(== \"a\" \"a\")", &mut env, val_bool(true));
+        assert_eval("(!= \"a\" \"b\")", &mut env, val_bool(true));
+    }
+
+    #[test]
+    fn len_counts_string_characters() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(len \"tank\")", &mut env, val_num(4));
+    }
+
+    #[test]
+    fn substr_slices_a_string() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(substr \"low tank\" 0 3)", &mut env, val_str("low"));
+    }
+
+    #[test]
+    fn format_concatenates_printed_values() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(format \"tank at \" 78.2)", &mut env, val_str("tank at 78.2"));
+    }
+
     #[test]
     fn subtract_one_number() {
         init();
@@ -665,8 +1074,8 @@ mod test {
     fn if_true() {
         init();
         let mut env = Env::new(None);
-        env.put("a".to_string(), val_num(1));
-        env.put("b".to_string(), val_num(2));
+        env.put("a".to_string(), val_num(1)).unwrap();
+        env.put("b".to_string(), val_num(2)).unwrap();
         assert_eval("(if (< a b) (+ a b) (- a b))", &mut env, val_num(3));
     }
 
@@ -674,17 +1083,60 @@ mod test {
     fn if_false() {
         init();
         let mut env = Env::new(None);
-        env.put("a".to_string(), val_num(1));
-        env.put("b".to_string(), val_num(2));
+        env.put("a".to_string(), val_num(1)).unwrap();
+        env.put("b".to_string(), val_num(2)).unwrap();
         assert_eval("(if (> a b) (+ a b) (- a b))", &mut env, val_num(-1));
     }
 
+    #[test]
+    fn if_never_evaluates_the_untaken_branch() {
+        init();
+        let mut env = Env::new(None);
+        // if `if` eagerly evaluated both branches, `(/ 1 0)` would panic on division by
+        // zero; the untaken branch here must never be reached.
+        assert_eval("(if (1) 42 (/ 1 0))", &mut env, val_num(42));
+        assert_eval("(if (nil) (/ 1 0) 42)", &mut env, val_num(42));
+    }
+
+    #[test]
+    fn if_with_no_else_yields_nil_on_a_falsy_test() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(if (nil) 42)", &mut env, val_bool(false));
+    }
+
+    #[test]
+    fn cond_returns_the_first_truthy_clause_and_skips_the_rest() {
+        init();
+        let mut env = Env::new(None);
+        env.put("a".to_string(), val_num(1)).unwrap();
+        assert_eval(
+            "(cond ((> a 5) (/ 1 0)) ((> a 0) \"positive\") (else (/ 1 0)))",
+            &mut env,
+            val_str("positive"),
+        );
+    }
+
+    #[test]
+    fn cond_falls_through_to_else() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(cond ((nil) 1) (else 2))", &mut env, val_num(2));
+    }
+
+    #[test]
+    fn cond_with_no_matching_clause_yields_nil() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(cond ((nil) 1) ((false) 2))", &mut env, val_bool(false));
+    }
+
     #[test]
     fn add_two_floats() {
         init();
         let mut env = Env::new(None);
-        env.put("a".to_string(), val_float(1.2));
-        env.put("b".to_string(), val_float(2.3));
+        env.put("a".to_string(), val_float(1.2)).unwrap();
+        env.put("b".to_string(), val_float(2.3)).unwrap();
         assert_eval("(+ a b)", &mut env, val_float(3.5));
     }
 
@@ -692,8 +1144,8 @@ mod test {
     fn add_float_and_num() {
         init();
         let mut env = Env::new(None);
-        env.put("a".to_string(), val_num(3));
-        env.put("b".to_string(), val_float(0.1415));
+        env.put("a".to_string(), val_num(3)).unwrap();
+        env.put("b".to_string(), val_float(0.1415)).unwrap();
         assert_eval("(+ a b)", &mut env, val_float(3.1415));
         assert_eval("(+ b a)", &mut env, val_float(3.1415));
     }
@@ -702,9 +1154,9 @@ mod test {
     fn compare_float_and_num() {
         init();
         let mut env = Env::new(None);
-        env.put("a".to_string(), val_num(3));
-        env.put("b".to_string(), val_float(0.1415));
-        env.put("c".to_string(), val_float(3.0));
+        env.put("a".to_string(), val_num(3)).unwrap();
+        env.put("b".to_string(), val_float(0.1415)).unwrap();
+        env.put("c".to_string(), val_float(3.0)).unwrap();
         assert_eval("(< a b)", &mut env, val_bool(false));
         assert_eval("(> a b)", &mut env, val_bool(true));
         assert_eval("(>= a c)", &mut env, val_bool(true));
@@ -718,8 +1170,8 @@ mod test {
     fn compare_two_times() {
         init();
         let mut env = Env::new(None);
-        env.put("t1".to_string(), val_time(NaiveTime::from_hms(9, 0, 0)));
-        env.put("t2".to_string(), val_time(NaiveTime::from_hms(10, 0, 0)));
+        env.put("t1".to_string(), val_time(NaiveTime::from_hms(9, 0, 0))).unwrap();
+        env.put("t2".to_string(), val_time(NaiveTime::from_hms(10, 0, 0))).unwrap();
         assert_eval("(> t1 t2)", &mut env, val_bool(false));
         assert_eval("(< t1 t2)", &mut env, val_bool(true));
         assert_eval("(>= t1 t2)", &mut env, val_bool(false));
@@ -732,8 +1184,8 @@ mod test {
     fn compare_two_dates() {
         init();
         let mut env = Env::new(None);
-        env.put("t1".to_string(), val_date(NaiveDate::from_ymd(2020, 3, 12)));
-        env.put("t2".to_string(), val_date(NaiveDate::from_ymd(2020, 3, 13)));
+        env.put("t1".to_string(), val_date(NaiveDate::from_ymd(2020, 3, 12))).unwrap();
+        env.put("t2".to_string(), val_date(NaiveDate::from_ymd(2020, 3, 13))).unwrap();
         assert_eval("(> t1 t2)", &mut env, val_bool(false));
         assert_eval("(< t1 t2)", &mut env, val_bool(true));
         assert_eval("(>= t1 t2)", &mut env, val_bool(false));
@@ -746,8 +1198,8 @@ mod test {
     fn compare_two_datetimes() {
         init();
         let mut env = Env::new(None);
-        env.put("t1".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(0, 0, 1)));
-        env.put("t2".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(0, 0, 2)));
+        env.put("t1".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(0, 0, 1))).unwrap();
+        env.put("t2".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(0, 0, 2))).unwrap();
         assert_eval("(> t1 t2)", &mut env, val_bool(false));
         assert_eval("(< t1 t2)", &mut env, val_bool(true));
         assert_eval("(>= t1 t2)", &mut env, val_bool(false));
@@ -760,8 +1212,8 @@ mod test {
     fn compare_date_and_datetime() {
         init();
         let mut env = Env::new(None);
-        env.put("t1".to_string(),  val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(0, 0, 1)));
-        env.put("t2".to_string(), val_time(NaiveTime::from_hms(10, 0, 0)));
+        env.put("t1".to_string(),  val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(0, 0, 1))).unwrap();
+        env.put("t2".to_string(), val_time(NaiveTime::from_hms(10, 0, 0))).unwrap();
         assert_eval("(> t1 t2)", &mut env, val_bool(false));
         assert_eval("(> t2 t1)", &mut env, val_bool(true));
     }
@@ -773,12 +1225,86 @@ mod test {
         let t1 = now.add(Duration::seconds(-1));
         let t2 = now.add(Duration::seconds(1));
         let mut env = Env::new(None);
-        env.put("t1".to_string(), val_datetime(t1));
-        env.put("t2".to_string(), val_datetime(t2));
+        env.put("t1".to_string(), val_datetime(t1)).unwrap();
+        env.put("t2".to_string(), val_datetime(t2)).unwrap();
         assert_eval("(> (now) t1)", &mut env, val_bool(true));
         assert_eval("(> (now) t2)", &mut env, val_bool(false));
     }
 
+    #[test]
+    fn subtracting_datetimes_yields_a_duration() {
+        init();
+        let mut env = Env::new(None);
+        env.put("t1".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(10, 0, 0))).unwrap();
+        env.put("t2".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(8, 0, 0))).unwrap();
+        assert_eval("(sub t1 t2)", &mut env, val_duration(Duration::hours(2)));
+    }
+
+    #[test]
+    fn subtracting_dates_yields_a_day_count_duration() {
+        init();
+        let mut env = Env::new(None);
+        env.put("t1".to_string(), val_date(NaiveDate::from_ymd(2020, 3, 13))).unwrap();
+        env.put("t2".to_string(), val_date(NaiveDate::from_ymd(2020, 3, 10))).unwrap();
+        assert_eval("(sub t1 t2)", &mut env, val_duration(Duration::days(3)));
+    }
+
+    #[test]
+    fn adding_a_duration_to_a_datetime_offsets_it() {
+        init();
+        let mut env = Env::new(None);
+        env.put("t1".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(8, 0, 0))).unwrap();
+        assert_eval("(add t1 (hours 2))", &mut env,
+            val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(10, 0, 0)));
+        assert_eval("(sub t1 (minutes 30))", &mut env,
+            val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(7, 30, 0)));
+    }
+
+    #[test]
+    fn duration_literals() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(seconds 30)", &mut env, val_duration(Duration::seconds(30)));
+        assert_eval("(hours 2)", &mut env, val_duration(Duration::hours(2)));
+        assert_eval("(minutes 90)", &mut env, val_duration(Duration::minutes(90)));
+        assert_eval("(days 1)", &mut env, val_duration(Duration::days(1)));
+        assert_eval("(add (hours 1) (minutes 30))", &mut env, val_duration(Duration::minutes(90)));
+    }
+
+    #[test]
+    fn multiplying_a_duration_by_a_number_scales_it() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(mul (hours 1) 3)", &mut env, val_duration(Duration::hours(3)));
+        assert_eval("(mul 3 (hours 1))", &mut env, val_duration(Duration::hours(3)));
+    }
+
+    #[test]
+    fn multiplying_a_duration_by_an_out_of_range_number_errors_instead_of_truncating() {
+        init();
+        let mut env = Env::new(None);
+        let res = eval(&mut env, &mut *parse("(mul (hours 1) 4294967296)").unwrap());
+        assert!(res.is_err(), "a multiplier outside i32's range should error instead of wrapping via `as i32`");
+    }
+
+    #[test]
+    fn comparing_a_datetime_against_a_shifted_one() {
+        init();
+        let mut env = Env::new(None);
+        env.put("t1".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(10, 0, 0))).unwrap();
+        assert_eval("(> t1 (sub t1 (hours 2)))", &mut env, val_bool(true));
+    }
+
+    #[test]
+    fn adding_two_datetimes_is_a_descriptive_error_not_a_panic() {
+        init();
+        let mut env = Env::new(None);
+        env.put("t1".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(10, 0, 0))).unwrap();
+        env.put("t2".to_string(), val_datetime(NaiveDate::from_ymd(2020, 3, 12).and_hms(8, 0, 0))).unwrap();
+        let res = eval(&mut env, &mut *parse("(add t1 t2)").unwrap());
+        assert!(res.is_err(), "adding two datetimes together doesn't make sense and should error, not panic");
+    }
+
     #[test]
     fn and_operator() {
         init();
@@ -793,8 +1319,26 @@ mod test {
     fn or_operator() {
         init();
         let mut env = Env::new(None);
-        assert_eval("(or (> 1 0) (< 0 1) (== 1 1) (42))", &mut env, val_num(42));
-        assert_eval("(or (nil) (nil) (1) (42))", &mut env, val_num(42));
+        // `or` returns the first truthy value, not the last one
+        assert_eval("(or (> 1 0) (< 0 1) (== 1 1) (42))", &mut env, val_bool(true));
+        assert_eval("(or (nil) (nil) (1) (42))", &mut env, val_num(1));
+        assert_eval("(or)", &mut env, val_bool(false));
+    }
+
+    #[test]
+    fn and_with_no_arguments_is_true() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(and)", &mut env, val_bool(true));
+    }
+
+    #[test]
+    fn or_short_circuits_before_a_later_error() {
+        init();
+        let mut env = Env::new(None);
+        // if `(/ 1 0)` were evaluated, this would panic on integer division by zero;
+        // `or` must never reach it once `(1)` has already decided the result.
+        assert_eval("(or (1) (/ 1 0))", &mut env, val_num(1));
     }
 
     #[test]
@@ -806,20 +1350,308 @@ mod test {
         assert_eval("(not (> 1 0))", &mut env, val_bool(false));
         assert_eval("(not (< 1 0))", &mut env, val_bool(true));
     }
-    
+
+    #[test]
+    fn def_binds_in_current_scope() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(def threshold 78)", &mut env, val_num(78));
+        assert_eval("(+ threshold 1)", &mut env, val_num(79));
+    }
+
+    #[test]
+    fn def_against_a_live_sensor_reading_is_rejected() {
+        init();
+        let mut env = Env::new(None);
+        env.add_constant("Tank_Temperature", val_float(75.9));
+        let res = eval(&mut env, &mut *parse("(def Tank_Temperature 1)").unwrap());
+        assert!(matches!(res, Err(RispError::ProtectedBinding(ref s)) if s == "Tank_Temperature"));
+    }
+
+    #[test]
+    fn let_binds_in_a_child_scope_that_does_not_escape() {
+        init();
+        let mut env = Env::new(None);
+        env.put("a".to_string(), val_num(1)).unwrap();
+        assert_eval("(let ((a 10) (b 20)) (+ a b))", &mut env, val_num(30));
+        // the outer `a` is unaffected by the `let`'s shadowing binding
+        assert_eval("a", &mut env, val_num(1));
+    }
+
+    #[test]
+    fn nan_comparisons_are_false_instead_of_erroring() {
+        init();
+        let mut env = Env::new(None);
+        env.put("nan".to_string(), val_float(f64::NAN)).unwrap();
+        env.put("one".to_string(), val_num(1)).unwrap();
+        assert_eval("(eq nan nan)", &mut env, val_bool(false));
+        assert_eval("(gt nan one)", &mut env, val_bool(false));
+        assert_eval("(lt nan one)", &mut env, val_bool(false));
+        assert_eval("(ge nan one)", &mut env, val_bool(false));
+        assert_eval("(le nan one)", &mut env, val_bool(false));
+        // distinctness is unaffected by NaN's weird equality: it's still "not equal to itself"
+        assert_eval("(ne nan nan)", &mut env, val_bool(true));
+    }
+
+    #[test]
+    fn let_bindings_can_see_earlier_bindings_in_the_same_let() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(let ((a 10) (b (+ a 5))) b)", &mut env, val_num(15));
+    }
+
+    #[test]
+    fn lambda_applies_to_arguments() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("((lambda (a b) (+ a b)) 1 2)", &mut env, val_num(3));
+    }
+
+    #[test]
+    fn lambda_captures_its_defining_scope() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(def threshold 78)", &mut env, val_num(78));
+        assert_eval("(def over-threshold (lambda (x) (> x threshold)))", &mut env,
+            val_lambda(vec!["x".to_string()], parse_expr("(> x threshold)"), Env::new(None)));
+        assert_eval("(over-threshold 80)", &mut env, val_bool(true));
+        assert_eval("(over-threshold 70)", &mut env, val_bool(false));
+    }
+
+    #[test]
+    fn lambda_wrong_arity_is_an_error() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(def add-two (lambda (a b) (+ a b)))", &mut env,
+            val_lambda(vec!["a".to_string(), "b".to_string()], parse_expr("(+ a b)"), Env::new(None)));
+        let res = eval(&mut env, &mut *parse("(add-two 1)").unwrap());
+        assert!(res.is_err(), "calling with the wrong number of arguments should error");
+    }
+
+    #[test]
+    fn def_bound_lambda_can_reference_itself_by_name() {
+        init();
+        let mut env = Env::new(None);
+        let expected = val_lambda(vec!["n".to_string()], parse_expr("loop"), Env::new(None));
+        assert_eval("(def loop (lambda (n) loop))", &mut env, expected.clone());
+        // calling it doesn't recurse here (the body just returns the symbol `loop`), but
+        // the lookup succeeding at all proves `loop` is visible to its own body.
+        assert_eval("(loop 1)", &mut env, expected);
+    }
+
+    #[test]
+    fn overflow_errors_by_default() {
+        init();
+        let mut env = Env::new(None);
+        let res = eval(&mut env, &mut *parse("(+ 9223372036854775807 1)").unwrap());
+        assert!(res.is_err(), "the default policy should error on overflow instead of wrapping");
+    }
+
+    #[test]
+    fn overflow_saturates_when_policy_is_saturate() {
+        init();
+        let mut env = Env::new(None);
+        env.set_overflow_policy(OverflowPolicy::Saturate);
+        assert_eval("(+ 9223372036854775807 1)", &mut env, val_num(i64::MAX));
+        assert_eval("(- -9223372036854775808 1)", &mut env, val_num(i64::MIN));
+        assert_eval("(* 9223372036854775807 2)", &mut env, val_num(i64::MAX));
+    }
+
+    #[test]
+    fn overflow_wraps_when_policy_is_wrap() {
+        init();
+        let mut env = Env::new(None);
+        env.set_overflow_policy(OverflowPolicy::Wrap);
+        assert_eval("(+ 9223372036854775807 1)", &mut env, val_num(i64::MIN));
+        assert_eval("(- -9223372036854775808 1)", &mut env, val_num(i64::MAX));
+        assert_eval("(* 9223372036854775807 2)", &mut env, val_num(-2));
+    }
+
+    #[test]
+    fn unary_negation_respects_overflow_policy() {
+        init();
+        let mut env = Env::new(None);
+        let res = eval(&mut env, &mut *parse("(- -9223372036854775808)").unwrap());
+        assert!(res.is_err(), "negating i64::MIN overflows and should error under the default policy");
+
+        env.set_overflow_policy(OverflowPolicy::Saturate);
+        assert_eval("(- -9223372036854775808)", &mut env, val_num(i64::MAX));
+
+        env.set_overflow_policy(OverflowPolicy::Wrap);
+        assert_eval("(- -9223372036854775808)", &mut env, val_num(i64::MIN));
+    }
+
+    #[test]
+    fn child_scope_inherits_overflow_policy() {
+        init();
+        let mut env = Env::new(None);
+        env.set_overflow_policy(OverflowPolicy::Saturate);
+        assert_eval("(let ((a 1)) (+ 9223372036854775807 a))", &mut env, val_num(i64::MAX));
+    }
+
+    #[test]
+    fn char_plus_num_advances_the_code_point() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(+ 'a' 1)", &mut env, val_char('b'));
+    }
+
+    #[test]
+    fn num_plus_char_returns_a_number() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(+ 1 'a')", &mut env, val_num(1 + 'a' as i64));
+    }
+
+    #[test]
+    fn char_minus_char_is_the_code_point_distance() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(- 'c' 'a')", &mut env, val_num(2));
+    }
+
+    #[test]
+    fn char_minus_num_shifts_back() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(- 'c' 2)", &mut env, val_char('a'));
+    }
+
+    #[test]
+    fn char_overflow_past_the_unicode_scalar_range_is_an_error() {
+        init();
+        let mut env = Env::new(None);
+        let res = eval(&mut env, &mut *parse("(+ '\u{10FFFF}' 1)").unwrap());
+        assert!(res.is_err(), "advancing past the last valid code point should error, not wrap");
+    }
+
+    #[test]
+    fn char_underflow_below_zero_is_an_error() {
+        init();
+        let mut env = Env::new(None);
+        let res = eval(&mut env, &mut *parse("(- '\u{0}' 1)").unwrap());
+        assert!(res.is_err(), "shifting before code point 0 should error, not wrap");
+    }
+
+    #[test]
+    fn char_overflow_into_a_surrogate_code_point_is_an_error() {
+        init();
+        let mut env = Env::new(None);
+        let res = eval(&mut env, &mut *parse("(+ '\u{D7FF}' 1)").unwrap());
+        assert!(res.is_err(), "landing in the UTF-16 surrogate range isn't a valid scalar value and should error");
+    }
+
+    #[test]
+    fn to_string_renders_common_values() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(to-string 42)", &mut env, val_str("42"));
+        assert_eval("(to-string true)", &mut env, val_str("true"));
+        assert_eval("(to-string false)", &mut env, val_str("false"));
+        assert_eval("(to-string \"tank\")", &mut env, val_str("tank"));
+    }
+
+    #[test]
+    fn to_string_renders_a_datetime_as_rfc3339_with_the_local_offset() {
+        init();
+        let naive = NaiveDate::from_ymd(2020, 3, 12).and_hms(8, 30, 0);
+        let expected = Local.from_local_datetime(&naive).single().unwrap().to_rfc3339();
+        let mut env = Env::new(None);
+        env.put("t".to_string(), val_datetime(naive)).unwrap();
+        assert_eval("(to-string t)", &mut env, val_str(&expected));
+    }
+
+    #[test]
+    fn to_num_parses_numeric_strings() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(to-num \"42\")", &mut env, val_num(42));
+        assert_eval("(to-num \"3.14\")", &mut env, val_float(3.14));
+    }
+
+    #[test]
+    fn to_num_on_a_non_numeric_string_is_an_error() {
+        init();
+        let mut env = Env::new(None);
+        let res = eval(&mut env, &mut *parse("(to-num \"tank\")").unwrap());
+        assert!(res.is_err(), "a non-numeric string should fail to convert");
+    }
+
+    #[test]
+    fn type_of_names_the_variant() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(type-of 1)", &mut env, val_str("num"));
+        assert_eval("(type-of 1.5)", &mut env, val_str("float"));
+        assert_eval("(type-of true)", &mut env, val_str("bool"));
+        assert_eval("(type-of \"tank\")", &mut env, val_str("string"));
+        assert_eval("(type-of 'a')", &mut env, val_str("char"));
+    }
+
+    #[test]
+    fn bitwise_and_masks_integers() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(& 6 3)", &mut env, val_num(2));
+    }
+
+    #[test]
+    fn bitwise_or_and_xor_on_integers() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(| 6 3)", &mut env, val_num(7));
+        assert_eval("(^ 6 3)", &mut env, val_num(5));
+    }
+
+    #[test]
+    fn bitwise_operators_are_eager_logical_operators_on_booleans() {
+        init();
+        let mut env = Env::new(None);
+        assert_eval("(| true false)", &mut env, val_bool(true));
+        assert_eval("(& true false)", &mut env, val_bool(false));
+        assert_eval("(^ true false)", &mut env, val_bool(true));
+        assert_eval("(^ true true)", &mut env, val_bool(false));
+    }
+
+    #[test]
+    fn bitwise_or_does_not_short_circuit_on_an_intermediate_false() {
+        init();
+        let mut env = Env::new(None);
+        // unlike the `or` special form, `|` always evaluates every argument; a `false` in
+        // the middle of the chain must not stop a later `true` from winning.
+        assert_eval("(| false false true)", &mut env, val_bool(true));
+    }
+
+    #[test]
+    fn bitwise_and_on_a_non_boolean_non_integer_operand_is_a_clear_error() {
+        init();
+        let mut env = Env::new(None);
+        let res = eval(&mut env, &mut *parse("(& \"tank\" 1)").unwrap());
+        assert!(res.is_err(), "mixing a string into a bitwise op should error, not coerce");
+    }
+
+    // Unwrap a single parsed expression out of its top-level `Risp` wrapper, for building
+    // expected `Val`s that compare against a real (non-top-level) parsed body.
+    fn parse_expr(s: &str) -> Box<Val> {
+        match *parse(s).unwrap() {
+            Val::Risp(mut forms) => forms.remove(0),
+            other => Box::new(other),
+        }
+    }
+
     fn assert_eval(s: &str, env: &mut Env, v: Box<Val>) {
         let mut parsed = match parse(s) {
             Ok(p) => *p,
             Err(err) => {
                 debug!("{}", err);
-                return assert!(false, err)
+                return assert!(false, "{}", err)
             }, 
         };
         let evaled =  match eval(env, &mut parsed) {
             Ok(v) => v,
             Err(err) => {
                 debug!("{}", err);
-                return assert!(false, err)
+                return assert!(false, "{}", err)
             }, 
         };
         assert_eq!(v, evaled);
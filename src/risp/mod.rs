@@ -0,0 +1,6 @@
+pub mod env;
+pub mod error;
+pub mod eval;
+pub mod parse;
+pub mod result;
+pub mod val;
@@ -66,6 +66,17 @@ fn val_read(parsed: Pair<Rule>) -> RispResult {
         },
         Rule::operator => Ok(val_sym(parsed.as_str())),
         Rule::symbol => Ok(val_sym(parsed.as_str())),
+        Rule::string => {
+            let s = parsed.as_str();
+            // strip the surrounding quotes the grammar matched
+            Ok(val_str(&s[1..s.len() - 1]))
+        }
+        Rule::char => {
+            let s = parsed.as_str();
+            // strip the surrounding single quotes the grammar matched
+            let c = s[1..s.len() - 1].chars().next().unwrap();
+            Ok(val_char(c))
+        }
         Rule::time => {
             let s = parsed.as_str();
             let t = NaiveTime::parse_from_str(s, "%H:%M:%S")?;
@@ -94,7 +105,7 @@ mod test {
     fn assert_parse_risp(input: &str, expected: &str) {
         let parsed = match parse(input) {
             Ok(p) => format!("{:?}", p),
-            Err(e) => return assert!(false, format!("Parse failed: {:?}", e))
+            Err(e) => return assert!(false, "Parse failed: {:?}", e)
         };
         assert_eq!(parsed, expected);
     }
@@ -103,7 +114,7 @@ mod test {
     fn parsing_nonsense_results_in_error() {
         init();
         let parsed = match parse("/|garbage|/") {
-            Ok(p) => return assert!(false, format!("Should not have parsed: {:?}", p)),
+            Ok(p) => return assert!(false, "Should not have parsed: {:?}", p),
             Err(e) => {}
         };
     }
@@ -198,7 +209,7 @@ mod test {
             Ok(p) => *p,
             Err(err) => {
                 debug!("{}", err);
-                return assert!(false, err)
+                return assert!(false, "{}", err)
             }
         };
 
@@ -241,5 +252,17 @@ mod test {
         init();
         assert_parse_risp("00:00:00", "Risp([Time(00:00:00)])");
     }
-    
+
+    #[test]
+    fn parse_string_literal() {
+        init();
+        assert_parse_risp("\"low tank\"", "Risp([Str(\"low tank\")])");
+    }
+
+    #[test]
+    fn parse_char_literal() {
+        init();
+        assert_parse_risp("'a'", "Risp([Char('a')])");
+    }
+
 }
\ No newline at end of file
@@ -7,7 +7,7 @@ use std::{
     cmp::Ordering,
     fmt
 };
-use chrono::{NaiveDateTime, NaiveDate, NaiveTime};
+use chrono::{Duration, NaiveDateTime, NaiveDate, NaiveTime};
 
 type ValChildren = Vec<Box<Val>>;
 pub type Builtin = fn(&mut Env, &mut Val) -> RispResult;
@@ -15,12 +15,24 @@ pub type Builtin = fn(&mut Env, &mut Val) -> RispResult;
 #[derive(Clone)]
 pub enum ValFun {
     Builtin(String, Builtin),
+    /// A user-defined function: `(lambda (params...) body)`. `closure` is the environment
+    /// the lambda was defined in, so the body can see names bound outside it even after
+    /// that scope has otherwise gone out of scope.
+    Lambda {
+        params: Vec<String>,
+        body: Box<Val>,
+        closure: Box<Env>,
+        /// The name this lambda was bound to via `def`, if any. `call` rebinds it to
+        /// itself inside its own call scope so a recursive self-call resolves correctly.
+        name: Option<String>,
+    },
 }
 
 impl fmt::Debug for ValFun {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ValFun::Builtin(name, _) => write!(f, "Builtin({})", name),
+            ValFun::Lambda { params, .. } => write!(f, "Lambda({:?})", params),
         }
     }
 }
@@ -29,17 +41,21 @@ impl fmt::Display for Val {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Val::Bool(b) => write!(formatter, "{}", b),
+            Val::Char(c) => write!(formatter, "{}", c),
             Val::Risp(_cells) => write!(formatter, "<toplevel>"),
             Val::Float(f) => write!(formatter, "{}", f),
             Val::Fun(lf) => match lf {
                 ValFun::Builtin(name, _) => write!(formatter, "<builtin: {}>", name),
+                ValFun::Lambda { params, .. } => write!(formatter, "<lambda: {}>", params.join(" ")),
             },
             Val::Num(n) => write!(formatter, "{}", n),
+            Val::Str(s) => write!(formatter, "{}", s),
             Val::Sym(s) => write!(formatter, "{}", s),
             Val::List(cell) => write!(formatter, "({})", val_expr_print(cell)),
             Val::Time(t) => write!(formatter, "{}", t),
             Val::Date(d) => write!(formatter, "{}", d),
             Val::DateTime(d) => write!(formatter, "{}", d),
+            Val::Duration(d) => write!(formatter, "{}s", d.num_seconds()),
         }
     }
 }
@@ -57,10 +73,14 @@ fn val_expr_print(cell: &[Box<Val>]) -> String {
 
 impl PartialEq for ValFun {
     fn eq(&self, other: &ValFun) -> bool {
-        match self {
-            ValFun::Builtin(name, _) => match other {
-                ValFun::Builtin(other_name, _) => name == other_name,
-            },
+        match (self, other) {
+            (ValFun::Builtin(name, _), ValFun::Builtin(other_name, _)) => name == other_name,
+            // Compare by signature, not by captured environment: two lambdas with the same
+            // params and body are the same function even if defined in different scopes.
+            (ValFun::Lambda { params, body, .. }, ValFun::Lambda { params: other_params, body: other_body, .. }) => {
+                params == other_params && body == other_body
+            }
+            _ => false,
         }
     }
 }
@@ -68,15 +88,18 @@ impl PartialEq for ValFun {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Val {
     Bool(bool),
+    Char(char),
     Float(f64),
     Fun(ValFun),
     List(ValChildren),
     Num(i64),
     Risp(ValChildren),
+    Str(String),
     Sym(String),
     Time(NaiveTime),
     Date(NaiveDate),
     DateTime(NaiveDateTime),
+    Duration(Duration),
 }
 
 impl Val {
@@ -102,6 +125,13 @@ impl Val {
             _ => Err(RispError::WrongType("bool".to_string(), format!("{}", self))),
         }
     }
+
+    pub fn as_char(&self) -> Result<char> {
+        match *self {
+            Val::Char(c) => Ok(c),
+            _ => Err(RispError::WrongType("char".to_string(), format!("{}", self))),
+        }
+    }
 }
 
 impl PartialOrd for Val {
@@ -117,6 +147,34 @@ impl PartialOrd for Val {
                Val::Float(o) => (*s as f64).partial_cmp(o),
                _ => None
            },
+           Val::Str(s) => match other {
+               Val::Str(o) => Some(s.cmp(o)),
+               _ => None
+           },
+           Val::Char(s) => match other {
+               Val::Char(o) => Some(s.cmp(o)),
+               _ => None
+           },
+           Val::Duration(s) => match other {
+               Val::Duration(o) => Some(s.cmp(o)),
+               _ => None
+           },
+           Val::Date(s) => match other {
+               Val::Date(o) => Some(s.cmp(o)),
+               _ => None
+           },
+           // A bare `Time` has no date, so a `Time`/`DateTime` comparison falls back to
+           // comparing the `DateTime`'s time-of-day component.
+           Val::Time(s) => match other {
+               Val::Time(o) => Some(s.cmp(o)),
+               Val::DateTime(o) => s.partial_cmp(&o.time()),
+               _ => None
+           },
+           Val::DateTime(s) => match other {
+               Val::DateTime(o) => Some(s.cmp(o)),
+               Val::Time(o) => s.time().partial_cmp(o),
+               _ => None
+           },
            _ => None
        }
    }
@@ -136,6 +194,14 @@ pub fn val_sym(s: &str) -> Box<Val> {
     Box::new(Val::Sym(s.into()))
 }
 
+pub fn val_str(s: &str) -> Box<Val> {
+    Box::new(Val::Str(s.into()))
+}
+
+pub fn val_char(c: char) -> Box<Val> {
+    Box::new(Val::Char(c))
+}
+
 pub fn val_list() -> Box<Val> {
     Box::new(Val::List(Vec::new()))
 }
@@ -144,6 +210,16 @@ pub fn val_builtin(f: Builtin, name: &str) -> Box<Val> {
     Box::new(Val::Fun(ValFun::Builtin(name.to_string(), f)))
 }
 
+pub fn val_lambda(params: Vec<String>, body: Box<Val>, closure: Env) -> Box<Val> {
+    Box::new(Val::Fun(ValFun::Lambda { params, body, closure: Box::new(closure), name: None }))
+}
+
+/// Like [`val_lambda`], but tagged with the name it was bound to via `def` so `call` can
+/// make it visible to itself for recursion.
+pub fn val_lambda_named(params: Vec<String>, body: Box<Val>, closure: Env, name: String) -> Box<Val> {
+    Box::new(Val::Fun(ValFun::Lambda { params, body, closure: Box::new(closure), name: Some(name) }))
+}
+
 pub fn val_bool(b: bool) -> Box<Val> {
     Box::new(Val::Bool(b))
 }
@@ -164,6 +240,10 @@ pub fn val_datetime(dt: NaiveDateTime) -> Box<Val> {
     Box::new(Val::DateTime(dt))
 }
 
+pub fn val_duration(d: Duration) -> Box<Val> {
+    Box::new(Val::Duration(d))
+}
+
 // Manipulating Children
 
 pub fn val_add(v: &mut Val, x: &Val) -> Result<()> {
@@ -177,6 +257,14 @@ pub fn val_add(v: &mut Val, x: &Val) -> Result<()> {
     Ok(())
 }
 
+pub fn val_peek(v: &mut Val, i: usize) -> RispResult {
+    match *v {
+        Val::Risp(ref children)
+        | Val::List(ref children) => Ok((&children[i]).clone()),
+        _ => Err(RispError::NoChildren),
+    }
+}
+
 pub fn val_pop(v: &mut Val, i: usize) -> RispResult {
     match *v {
         Val::Risp(ref mut children)
@@ -197,4 +285,43 @@ mod test {
     fn compare_floats() {
         assert!(Val::Float(1.0) > Val::Float(0.0));
     }
+
+    #[test]
+    fn compare_strings() {
+        assert!(Val::Str("b".to_string()) > Val::Str("a".to_string()));
+    }
+
+    #[test]
+    fn compare_durations() {
+        assert!(Val::Duration(Duration::hours(2)) > Val::Duration(Duration::minutes(30)));
+    }
+
+    #[test]
+    fn compare_chars() {
+        assert!(Val::Char('b') > Val::Char('a'));
+    }
+
+    #[test]
+    fn compare_dates() {
+        assert!(Val::Date(NaiveDate::from_ymd(2020, 3, 13)) > Val::Date(NaiveDate::from_ymd(2020, 3, 12)));
+    }
+
+    #[test]
+    fn compare_times() {
+        assert!(Val::Time(NaiveTime::from_hms(10, 0, 0)) > Val::Time(NaiveTime::from_hms(9, 0, 0)));
+    }
+
+    #[test]
+    fn compare_datetimes() {
+        assert!(Val::DateTime(NaiveDate::from_ymd(2020, 3, 12).and_hms(0, 0, 2))
+            > Val::DateTime(NaiveDate::from_ymd(2020, 3, 12).and_hms(0, 0, 1)));
+    }
+
+    #[test]
+    fn compare_time_against_a_datetimes_time_of_day() {
+        let datetime = Val::DateTime(NaiveDate::from_ymd(2020, 3, 12).and_hms(0, 0, 1));
+        let time = Val::Time(NaiveTime::from_hms(10, 0, 0));
+        assert!(time > datetime);
+        assert!(datetime < time);
+    }
 }
\ No newline at end of file